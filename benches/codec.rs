@@ -0,0 +1,134 @@
+use std::hint::black_box;
+
+use bytes::BytesMut;
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+use rust_redis_server::{
+    BulkString, RespCodec, RespDecode, RespEncode, RespFrame, RespFrameArray, RespParser,
+    SimpleString,
+};
+use tokio_util::codec::Decoder;
+
+const LARGE_BULK_LEN: usize = 1024 * 1024;
+const NESTING_DEPTH: usize = 100;
+const PIPELINE_LEN: usize = 1000;
+
+fn simple_string() -> RespFrame {
+    SimpleString::new("OK").into()
+}
+
+fn large_bulk_string() -> RespFrame {
+    BulkString::new(vec![b'x'; LARGE_BULK_LEN]).into()
+}
+
+fn nested_array() -> RespFrame {
+    (0..NESTING_DEPTH).fold(RespFrame::from(1), |frame, _| {
+        RespFrameArray::new(vec![frame]).into()
+    })
+}
+
+// `SET key:<i> value:<i>` commands as a client pipelines them
+fn pipelined_commands() -> Vec<u8> {
+    let mut data = Vec::new();
+    for i in 0..PIPELINE_LEN {
+        let command = RespFrameArray::new(vec![
+            b"SET".into(),
+            BulkString::new(format!("key:{}", i)).into(),
+            BulkString::new(format!("value:{}", i)).into(),
+        ]);
+        command.encode_to(&mut data);
+    }
+    data
+}
+
+fn bench_encode(c: &mut Criterion) {
+    let mut group = c.benchmark_group("encode");
+    let cases = [
+        ("simple_string", simple_string()),
+        ("large_bulk_string", large_bulk_string()),
+        ("nested_array", nested_array()),
+    ];
+    for (name, frame) in cases {
+        let mut buf = BytesMut::with_capacity(frame.encoded_len());
+        group.throughput(Throughput::Bytes(frame.encoded_len() as u64));
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                buf.clear();
+                black_box(&frame).encode_to(&mut buf);
+            })
+        });
+    }
+    group.finish();
+}
+
+fn bench_decode(c: &mut Criterion) {
+    let mut group = c.benchmark_group("decode");
+    let cases = [
+        ("simple_string", simple_string().encode()),
+        ("large_bulk_string", large_bulk_string().encode()),
+        ("nested_array", nested_array().encode()),
+    ];
+    for (name, data) in cases {
+        group.throughput(Throughput::Bytes(data.len() as u64));
+        group.bench_function(name, |b| {
+            b.iter_batched_ref(
+                || BytesMut::from(&data[..]),
+                |buf| RespFrame::decode(buf).unwrap(),
+                BatchSize::SmallInput,
+            )
+        });
+        group.bench_function(format!("{}_parser", name), |b| {
+            b.iter_batched_ref(
+                || BytesMut::from(&data[..]),
+                |buf| RespParser::new().parse(buf).unwrap(),
+                BatchSize::SmallInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+fn bench_pipeline(c: &mut Criterion) {
+    let mut group = c.benchmark_group("pipeline");
+    let data = pipelined_commands();
+    group.throughput(Throughput::Elements(PIPELINE_LEN as u64));
+
+    group.bench_function("decode", |b| {
+        b.iter_batched_ref(
+            || BytesMut::from(&data[..]),
+            |buf| {
+                while !buf.is_empty() {
+                    black_box(RespFrame::decode(buf).unwrap());
+                }
+            },
+            BatchSize::SmallInput,
+        )
+    });
+
+    // the way the server reads: the codec over whatever arrived in one read
+    group.bench_function("codec", |b| {
+        b.iter_batched_ref(
+            || (RespCodec::new(), BytesMut::from(&data[..])),
+            |(codec, buf)| {
+                while let Some(frame) = codec.decode(buf).unwrap() {
+                    black_box(frame);
+                }
+            },
+            BatchSize::SmallInput,
+        )
+    });
+
+    let replies: Vec<RespFrame> = (0..PIPELINE_LEN).map(|_| simple_string()).collect();
+    let mut buf = BytesMut::new();
+    group.bench_function("encode_replies", |b| {
+        b.iter(|| {
+            buf.clear();
+            for reply in &replies {
+                reply.encode_to(&mut buf);
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_encode, bench_decode, bench_pipeline);
+criterion_main!(benches);
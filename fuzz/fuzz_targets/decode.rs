@@ -0,0 +1,20 @@
+#![no_main]
+
+use bytes::BytesMut;
+use libfuzzer_sys::fuzz_target;
+use rust_redis_server::{RespDecode, RespEncode, RespFrame, RespParser};
+
+// decoding untrusted input must never panic, whichever way the bytes are fed in
+fuzz_target!(|data: &[u8]| {
+    let mut buf = BytesMut::from(data);
+    while let Ok(frame) = RespFrame::decode(&mut buf) {
+        assert_eq!(frame.encoded_len(), frame.encode().len());
+    }
+
+    let mut parser = RespParser::new();
+    let mut buf = BytesMut::new();
+    for chunk in data.chunks(7) {
+        buf.extend_from_slice(chunk);
+        while parser.parse_request(&mut buf).is_ok() {}
+    }
+});
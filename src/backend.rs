@@ -0,0 +1,368 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, RwLock,
+    },
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use bytes::Bytes;
+
+// how often the active expiry runs, like redis' default `hz 10`
+const ACTIVE_EXPIRE_PERIOD: Duration = Duration::from_millis(100);
+// the keys sampled at once and the share of them still expired, in percent, that makes the
+// cycle sample again, as in redis' `activeExpireCycle`
+const ACTIVE_EXPIRE_KEYS_PER_LOOP: usize = 20;
+const ACTIVE_EXPIRE_ACCEPTABLE_STALE: usize = 10;
+// a quarter of the period, so clients are never starved for long
+const ACTIVE_EXPIRE_CYCLE_TIME: Duration = Duration::from_millis(25);
+
+/// The keyspace shared by every connection. Cloning is cheap and clones share the same data.
+#[derive(Debug, Clone)]
+pub struct Backend {
+    keyspace: Arc<RwLock<Keyspace>>,
+    clock: Arc<dyn Clock>,
+}
+
+/// Where the backend reads the current time from.
+pub trait Clock: fmt::Debug + Send + Sync {
+    /// Milliseconds since the unix epoch.
+    fn now_ms(&self) -> u64;
+}
+
+/// The wall clock.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+/// A clock that only moves when told to, for tests. Clones share the same time.
+#[derive(Debug, Clone, Default)]
+pub struct ManualClock(Arc<AtomicU64>);
+
+#[derive(Debug, Default)]
+pub struct Keyspace {
+    entries: HashMap<Bytes, Entry>,
+    /// The keys with a ttl, which the active expiry samples from.
+    volatile: VolatileKeys,
+}
+
+// a set that can also pick a random member in constant time
+#[derive(Debug, Default)]
+struct VolatileKeys {
+    keys: Vec<Bytes>,
+    positions: HashMap<Bytes, usize>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub value: Bytes,
+    /// When the key expires, in milliseconds since the unix epoch.
+    pub expires_at: Option<u64>,
+}
+
+impl Backend {
+    pub fn new() -> Self {
+        Self::with_clock(SystemClock)
+    }
+
+    pub fn with_clock(clock: impl Clock + 'static) -> Self {
+        Backend {
+            keyspace: Default::default(),
+            clock: Arc::new(clock),
+        }
+    }
+
+    /// Runs `f` with shared access to the keyspace and the current time in unix milliseconds.
+    pub fn read<R>(&self, f: impl FnOnce(&Keyspace, u64) -> R) -> R {
+        let keyspace = self.keyspace.read().unwrap_or_else(|e| e.into_inner());
+        f(&keyspace, self.clock.now_ms())
+    }
+
+    /// Runs `f` with exclusive access to the keyspace, so everything it does is atomic.
+    pub fn write<R>(&self, f: impl FnOnce(&mut Keyspace, u64) -> R) -> R {
+        let mut keyspace = self.keyspace.write().unwrap_or_else(|e| e.into_inner());
+        f(&mut keyspace, self.clock.now_ms())
+    }
+
+    /// Reclaims expired keys nobody looks up anymore, returning how many were removed.
+    ///
+    /// Like redis, random keys with a ttl are sampled and the expired ones removed, sampling
+    /// again while many of them were expired. The lock is released between samples and the
+    /// whole cycle is bounded in time, so some expired keys may survive until the next one.
+    pub fn active_expire_cycle(&self) -> usize {
+        let start = Instant::now();
+        let mut reclaimed = 0;
+        loop {
+            let (sampled, expired) = self
+                .write(|keyspace, now| keyspace.sample_expired(ACTIVE_EXPIRE_KEYS_PER_LOOP, now));
+            reclaimed += expired;
+            if sampled == 0
+                || expired * 100 <= sampled * ACTIVE_EXPIRE_ACCEPTABLE_STALE
+                || start.elapsed() >= ACTIVE_EXPIRE_CYCLE_TIME
+            {
+                return reclaimed;
+            }
+        }
+    }
+
+    /// Runs the active expiry cycle periodically, forever.
+    pub async fn active_expire(self) {
+        let mut interval = tokio::time::interval(ACTIVE_EXPIRE_PERIOD);
+        loop {
+            interval.tick().await;
+            self.active_expire_cycle();
+        }
+    }
+}
+
+impl Default for Backend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for SystemClock {
+    fn now_ms(&self) -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_millis() as u64)
+    }
+}
+
+impl ManualClock {
+    pub fn new(now_ms: u64) -> Self {
+        ManualClock(Arc::new(AtomicU64::new(now_ms)))
+    }
+
+    pub fn set(&self, now_ms: u64) {
+        self.0.store(now_ms, Ordering::SeqCst);
+    }
+
+    pub fn advance(&self, ms: u64) {
+        self.0.fetch_add(ms, Ordering::SeqCst);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now_ms(&self) -> u64 {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+// Expired entries are invisible to every lookup; they are dropped once a writer touches them.
+impl Keyspace {
+    pub fn get(&self, key: &[u8], now: u64) -> Option<&Entry> {
+        self.entries.get(key).filter(|entry| !entry.is_expired(now))
+    }
+
+    pub fn get_mut(&mut self, key: &[u8], now: u64) -> Option<&mut Entry> {
+        self.remove_expired(key, now);
+        self.entries.get_mut(key)
+    }
+
+    pub fn contains(&self, key: &[u8], now: u64) -> bool {
+        self.get(key, now).is_some()
+    }
+
+    /// Stores `entry` under a copy of `key`, so a key sliced out of a request doesn't keep the
+    /// whole read buffer alive. Values are stored as given, see [`Entry::new`].
+    pub fn insert(&mut self, key: Bytes, entry: Entry) {
+        let key = Bytes::copy_from_slice(&key);
+        match entry.expires_at {
+            Some(_) => self.volatile.insert(key.clone()),
+            None => self.volatile.remove(&key),
+        }
+        self.entries.insert(key, entry);
+    }
+
+    pub fn remove(&mut self, key: &[u8], now: u64) -> Option<Entry> {
+        self.volatile.remove(key);
+        self.entries
+            .remove(key)
+            .filter(|entry| !entry.is_expired(now))
+    }
+
+    /// Changes when `key` expires, `None` making it persistent. Returns `false` if there is
+    /// no such key.
+    ///
+    /// The ttl must be changed here rather than through [`Keyspace::get_mut`], so the active
+    /// expiry knows about it.
+    pub fn set_expiry(&mut self, key: &[u8], expires_at: Option<u64>, now: u64) -> bool {
+        self.remove_expired(key, now);
+        let Some(entry) = self.entries.get_mut(key) else {
+            return false;
+        };
+        entry.expires_at = expires_at;
+        match expires_at {
+            Some(_) => self.volatile.insert(Bytes::copy_from_slice(key)),
+            None => self.volatile.remove(key),
+        }
+        true
+    }
+
+    // checks up to `count` random keys with a ttl, returning how many were checked and how
+    // many of those had expired and were removed
+    fn sample_expired(&mut self, count: usize, now: u64) -> (usize, usize) {
+        let sampled = count.min(self.volatile.len());
+        let mut expired = 0;
+        for _ in 0..sampled {
+            let Some(key) = self.volatile.random().cloned() else {
+                break;
+            };
+            if self.remove_expired(&key, now) {
+                expired += 1;
+            }
+        }
+        (sampled, expired)
+    }
+
+    fn remove_expired(&mut self, key: &[u8], now: u64) -> bool {
+        let expired = self
+            .entries
+            .get(key)
+            .is_some_and(|entry| entry.is_expired(now));
+        if expired {
+            self.entries.remove(key);
+            self.volatile.remove(key);
+        }
+        expired
+    }
+}
+
+impl VolatileKeys {
+    fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    fn insert(&mut self, key: Bytes) {
+        if !self.positions.contains_key(&key) {
+            self.positions.insert(key.clone(), self.keys.len());
+            self.keys.push(key);
+        }
+    }
+
+    fn remove(&mut self, key: &[u8]) {
+        let Some(position) = self.positions.remove(key) else {
+            return;
+        };
+        self.keys.swap_remove(position);
+        // the last key took the place of the removed one
+        if let Some(moved) = self.keys.get(position) {
+            self.positions.insert(moved.clone(), position);
+        }
+    }
+
+    fn random(&self) -> Option<&Bytes> {
+        if self.keys.is_empty() {
+            return None;
+        }
+        self.keys.get(fastrand::usize(..self.keys.len()))
+    }
+}
+
+impl Entry {
+    /// The value is kept as given, copy one sliced out of a request first.
+    pub fn new(value: impl Into<Bytes>) -> Self {
+        Entry {
+            value: value.into(),
+            expires_at: None,
+        }
+    }
+
+    pub fn with_expiry(value: impl Into<Bytes>, expires_at: Option<u64>) -> Self {
+        Entry {
+            value: value.into(),
+            expires_at,
+        }
+    }
+
+    pub fn is_expired(&self, now: u64) -> bool {
+        self.expires_at.is_some_and(|at| at <= now)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keyspace_hides_expired_entries() {
+        let mut keyspace = Keyspace::default();
+        keyspace.insert(Bytes::from("a"), Entry::with_expiry("1", Some(100)));
+        keyspace.insert(Bytes::from("b"), Entry::new("2"));
+
+        assert!(keyspace.contains(b"a", 99));
+        assert!(!keyspace.contains(b"a", 100));
+        assert!(keyspace.get_mut(b"a", 100).is_none());
+        assert!(!keyspace.entries.contains_key(b"a".as_slice()));
+        assert_eq!(keyspace.remove(b"b", 100), Some(Entry::new("2")));
+    }
+
+    #[test]
+    fn test_keyspace_copies_keys() {
+        let mut keyspace = Keyspace::default();
+        let buf = Bytes::from("*2\r\n$1\r\na\r\n");
+        let key = buf.slice(8..9);
+        keyspace.insert(key.clone(), Entry::with_expiry("1", Some(100)));
+
+        let (stored, _) = keyspace.entries.get_key_value(b"a".as_slice()).unwrap();
+        assert_ne!(stored.as_ptr(), key.as_ptr());
+        assert_ne!(keyspace.volatile.keys[0].as_ptr(), key.as_ptr());
+    }
+
+    #[test]
+    fn test_keyspace_tracks_volatile_keys() {
+        let mut keyspace = Keyspace::default();
+        keyspace.insert(Bytes::from("a"), Entry::with_expiry("1", Some(100)));
+        keyspace.insert(Bytes::from("b"), Entry::with_expiry("2", Some(100)));
+        keyspace.insert(Bytes::from("c"), Entry::new("3"));
+        assert_eq!(keyspace.volatile.len(), 2);
+
+        // overwritten without a ttl, persisted, and given a ttl
+        keyspace.insert(Bytes::from("a"), Entry::new("1"));
+        assert!(keyspace.set_expiry(b"b", None, 0));
+        assert!(keyspace.set_expiry(b"c", Some(100), 0));
+        assert!(!keyspace.set_expiry(b"d", Some(100), 0));
+        assert_eq!(keyspace.volatile.keys, vec![Bytes::from("c")]);
+        assert_eq!(keyspace.volatile.positions.get(b"c".as_slice()), Some(&0));
+
+        keyspace.remove(b"c", 0);
+        assert_eq!(keyspace.volatile.len(), 0);
+    }
+
+    #[test]
+    fn test_active_expire_cycle() {
+        let clock = ManualClock::new(1000);
+        let backend = Backend::with_clock(clock.clone());
+        backend.write(|keyspace, now| {
+            for i in 0..200 {
+                let expires_at = if i % 2 == 0 { now + 10 } else { now + 1000 };
+                keyspace.insert(
+                    i.to_string().into(),
+                    Entry::with_expiry("v", Some(expires_at)),
+                );
+            }
+            keyspace.insert("persistent".into(), Entry::new("v"));
+        });
+        assert_eq!(backend.active_expire_cycle(), 0);
+
+        clock.advance(10);
+        // sampling stops once few of the sampled keys are expired, so some may survive
+        let reclaimed = backend.active_expire_cycle();
+        assert!((1..=100).contains(&reclaimed), "reclaimed {}", reclaimed);
+        backend.read(|keyspace, _| {
+            assert_eq!(keyspace.entries.len(), 201 - reclaimed);
+            assert_eq!(keyspace.volatile.len(), 200 - reclaimed);
+        });
+
+        // every sampled key is expired now, so the cycle goes on until none is left
+        clock.advance(1000);
+        backend.active_expire_cycle();
+        backend.read(|keyspace, _| {
+            assert_eq!(keyspace.volatile.len(), 0);
+            assert!(keyspace.entries.contains_key(b"persistent".as_slice()));
+            assert_eq!(keyspace.entries.len(), 1);
+        });
+    }
+}
@@ -0,0 +1,101 @@
+use bytes::Bytes;
+
+use crate::{
+    cmd::{fixed_args, CommandError, CommandExecutor, CommandSpec},
+    Backend, BulkString, RespFrame, SimpleString,
+};
+
+/// `PING [message]`
+#[derive(Debug, Default, PartialEq)]
+pub struct Ping {
+    message: Option<Bytes>,
+}
+
+/// `ECHO message`
+#[derive(Debug, PartialEq)]
+pub struct Echo {
+    message: Bytes,
+}
+
+impl Ping {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_message(message: impl Into<Bytes>) -> Self {
+        Ping {
+            message: Some(message.into()),
+        }
+    }
+}
+
+impl Echo {
+    pub fn new(message: impl Into<Bytes>) -> Self {
+        Echo {
+            message: message.into(),
+        }
+    }
+}
+
+impl CommandSpec for Ping {
+    const NAME: &'static str = "ping";
+    const ARITY: isize = -1;
+
+    fn parse(args: Vec<Bytes>) -> Result<Self, CommandError> {
+        if args.len() > 1 {
+            return Err(CommandError::WrongArity(Self::NAME));
+        }
+        Ok(Ping {
+            message: args.into_iter().next(),
+        })
+    }
+}
+
+impl CommandSpec for Echo {
+    const NAME: &'static str = "echo";
+    const ARITY: isize = 2;
+
+    fn parse(args: Vec<Bytes>) -> Result<Self, CommandError> {
+        let [message] = fixed_args(Self::NAME, args)?;
+        Ok(Echo { message })
+    }
+}
+
+impl CommandExecutor for Ping {
+    fn execute(self, _: &Backend) -> Result<RespFrame, CommandError> {
+        Ok(match self.message {
+            Some(message) => BulkString::from(message).into(),
+            None => SimpleString::new("PONG").into(),
+        })
+    }
+}
+
+impl CommandExecutor for Echo {
+    fn execute(self, _: &Backend) -> Result<RespFrame, CommandError> {
+        Ok(BulkString::from(self.message).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+
+    #[test]
+    fn test_ping_execute() -> Result<()> {
+        let backend = Backend::new();
+        assert_eq!(
+            Ping::new().execute(&backend)?,
+            SimpleString::new("PONG").into()
+        );
+        assert_eq!(Ping::with_message("hi").execute(&backend)?, b"hi".into());
+        Ok(())
+    }
+
+    #[test]
+    fn test_echo_execute() -> Result<()> {
+        let backend = Backend::new();
+        assert_eq!(Echo::new("hello").execute(&backend)?, b"hello".into());
+        Ok(())
+    }
+}
@@ -0,0 +1,315 @@
+use std::{num::NonZeroU64, str::FromStr};
+
+use bigdecimal::{BigDecimal, RoundingMode};
+use bytes::Bytes;
+
+use crate::{
+    backend::Entry,
+    cmd::{fixed_args, parse_integer, CommandError, CommandExecutor, CommandSpec},
+    Backend, BigNumber, BulkString, RespFrame,
+};
+
+/// `INCR key`
+#[derive(Debug, PartialEq)]
+pub struct Incr {
+    key: Bytes,
+}
+
+/// `DECR key`
+#[derive(Debug, PartialEq)]
+pub struct Decr {
+    key: Bytes,
+}
+
+/// `INCRBY key increment`
+#[derive(Debug, PartialEq)]
+pub struct IncrBy {
+    key: Bytes,
+    increment: i64,
+}
+
+/// `DECRBY key decrement`
+#[derive(Debug, PartialEq)]
+pub struct DecrBy {
+    key: Bytes,
+    decrement: i64,
+}
+
+/// `INCRBYFLOAT key increment`
+#[derive(Debug, PartialEq)]
+pub struct IncrByFloat {
+    key: Bytes,
+    increment: f64,
+}
+
+impl CommandSpec for Incr {
+    const NAME: &'static str = "incr";
+    const ARITY: isize = 2;
+
+    fn parse(args: Vec<Bytes>) -> Result<Self, CommandError> {
+        let [key] = fixed_args(Self::NAME, args)?;
+        Ok(Incr { key })
+    }
+}
+
+impl CommandSpec for Decr {
+    const NAME: &'static str = "decr";
+    const ARITY: isize = 2;
+
+    fn parse(args: Vec<Bytes>) -> Result<Self, CommandError> {
+        let [key] = fixed_args(Self::NAME, args)?;
+        Ok(Decr { key })
+    }
+}
+
+impl CommandSpec for IncrBy {
+    const NAME: &'static str = "incrby";
+    const ARITY: isize = 3;
+
+    fn parse(args: Vec<Bytes>) -> Result<Self, CommandError> {
+        let [key, increment] = fixed_args(Self::NAME, args)?;
+        Ok(IncrBy {
+            key,
+            increment: parse_integer(&increment)?,
+        })
+    }
+}
+
+impl CommandSpec for DecrBy {
+    const NAME: &'static str = "decrby";
+    const ARITY: isize = 3;
+
+    fn parse(args: Vec<Bytes>) -> Result<Self, CommandError> {
+        let [key, decrement] = fixed_args(Self::NAME, args)?;
+        let decrement = parse_integer(&decrement)?;
+        // it can't be negated into an increment
+        if decrement == i64::MIN {
+            return Err(CommandError::InvalidArgument("decrement would overflow"));
+        }
+        Ok(DecrBy { key, decrement })
+    }
+}
+
+impl CommandSpec for IncrByFloat {
+    const NAME: &'static str = "incrbyfloat";
+    const ARITY: isize = 3;
+
+    fn parse(args: Vec<Bytes>) -> Result<Self, CommandError> {
+        let [key, increment] = fixed_args(Self::NAME, args)?;
+        Ok(IncrByFloat {
+            key,
+            increment: parse_float(&increment)?,
+        })
+    }
+}
+
+impl CommandExecutor for Incr {
+    fn execute(self, backend: &Backend) -> Result<RespFrame, CommandError> {
+        incr_by(backend, self.key, 1)
+    }
+}
+
+impl CommandExecutor for Decr {
+    fn execute(self, backend: &Backend) -> Result<RespFrame, CommandError> {
+        incr_by(backend, self.key, -1)
+    }
+}
+
+impl CommandExecutor for IncrBy {
+    fn execute(self, backend: &Backend) -> Result<RespFrame, CommandError> {
+        incr_by(backend, self.key, self.increment)
+    }
+}
+
+impl CommandExecutor for DecrBy {
+    fn execute(self, backend: &Backend) -> Result<RespFrame, CommandError> {
+        incr_by(backend, self.key, -self.decrement)
+    }
+}
+
+impl CommandExecutor for IncrByFloat {
+    fn execute(self, backend: &Backend) -> Result<RespFrame, CommandError> {
+        backend.write(|keyspace, now| {
+            let entry = keyspace.get_mut(&self.key, now);
+            let current = match &entry {
+                Some(entry) => parse_float(&entry.value)?,
+                None => 0.0,
+            };
+            let value = current + self.increment;
+            if !value.is_finite() {
+                return Err(CommandError::InvalidArgument(
+                    "increment would produce NaN or Infinity",
+                ));
+            }
+            let value = Bytes::from(float_sum_text(current, self.increment));
+            match entry {
+                Some(entry) => entry.value = value.clone(),
+                None => keyspace.insert(self.key, Entry::new(value.clone())),
+            }
+            Ok(BulkString::new(value).into())
+        })
+    }
+}
+
+// adds `delta` to the integer stored at `key`, a missing key counts as 0 and the ttl is kept
+fn incr_by(backend: &Backend, key: Bytes, delta: i64) -> Result<RespFrame, CommandError> {
+    backend.write(|keyspace, now| {
+        let entry = keyspace.get_mut(&key, now);
+        let current = match &entry {
+            Some(entry) => parse_integer(&entry.value)?,
+            None => 0,
+        };
+        let value = current.checked_add(delta).ok_or(CommandError::Overflow)?;
+        let bytes = Bytes::from(value.to_string());
+        match entry {
+            Some(entry) => entry.value = bytes,
+            None => keyspace.insert(key, Entry::new(bytes)),
+        }
+        Ok(RespFrame::from(value))
+    })
+}
+
+// Redis adds in a `long double` and prints the sum with `%.17Lf`, trailing zeros trimmed, so
+// `0.1 + 0.2` is `0.3`. The extra precision is emulated by adding the shortest decimals that
+// read back as `a` and `b`, keeping the 17 significant digits a double holds and at most 17
+// decimal places.
+fn float_sum_text(a: f64, b: f64) -> String {
+    let decimal = |f: f64| BigDecimal::from_str(&f.to_string()).expect("a finite float");
+    let digits = NonZeroU64::new(17).expect("not zero");
+    let sum = (decimal(a) + decimal(b))
+        .with_precision_round(digits, RoundingMode::HalfEven)
+        .with_scale_round(17, RoundingMode::HalfEven);
+    BigNumber(sum.normalized()).to_plain_string()
+}
+
+// like redis' `string2ld`: no surrounding whitespace and never NaN, but infinities parse
+fn parse_float(arg: &[u8]) -> Result<f64, CommandError> {
+    std::str::from_utf8(arg)
+        .ok()
+        .and_then(|s| s.parse::<f64>().ok())
+        .filter(|value| !value.is_nan())
+        .ok_or(CommandError::NotFloat)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{cmd::tests::run, BulkNullString};
+    use anyhow::Result;
+
+    #[test]
+    fn test_incr_decr() -> Result<()> {
+        let backend = Backend::new();
+        assert_eq!(run(&backend, &["INCR", "k"])?, 1.into());
+        assert_eq!(run(&backend, &["INCRBY", "k", "41"])?, 42.into());
+        assert_eq!(run(&backend, &["DECR", "k"])?, 41.into());
+        assert_eq!(run(&backend, &["DECRBY", "k", "-9"])?, 50.into());
+        assert_eq!(run(&backend, &["DECRBY", "other", "5"])?, (-5).into());
+        assert_eq!(run(&backend, &["GET", "k"])?, b"50".into());
+        Ok(())
+    }
+
+    #[test]
+    fn test_incr_not_integer() -> Result<()> {
+        let backend = Backend::new();
+        for value in ["abc", "1.5", " 1", "+1", "01", ""] {
+            run(&backend, &["SET", "k", value])?;
+            assert_eq!(run(&backend, &["INCR", "k"]), Err(CommandError::NotInteger));
+        }
+        assert_eq!(
+            run(&backend, &["INCRBY", "n", "x"]),
+            Err(CommandError::NotInteger)
+        );
+        assert_eq!(
+            run(&backend, &["INCRBY", "n", "9223372036854775808"]),
+            Err(CommandError::NotInteger)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_incr_overflow() -> Result<()> {
+        let backend = Backend::new();
+        run(&backend, &["SET", "k", "9223372036854775807"])?;
+        let err = run(&backend, &["INCR", "k"]).unwrap_err();
+        assert_eq!(err.to_string(), "ERR increment or decrement would overflow");
+        assert_eq!(run(&backend, &["GET", "k"])?, b"9223372036854775807".into());
+
+        run(&backend, &["SET", "k", "-9223372036854775808"])?;
+        assert_eq!(run(&backend, &["DECR", "k"]), Err(CommandError::Overflow));
+        let err = run(&backend, &["DECRBY", "k", "-9223372036854775808"]).unwrap_err();
+        assert_eq!(err.to_string(), "ERR decrement would overflow");
+        Ok(())
+    }
+
+    #[test]
+    fn test_incr_keeps_ttl() -> Result<()> {
+        let backend = Backend::new();
+        run(&backend, &["SET", "k", "1", "PX", "100000"])?;
+        run(&backend, &["INCR", "k"])?;
+        run(&backend, &["INCRBYFLOAT", "k", "0.5"])?;
+        let entry = backend.read(|keyspace, now| keyspace.get(b"k", now).cloned());
+        assert!(entry.is_some_and(|entry| entry.expires_at.is_some()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_incrbyfloat() -> Result<()> {
+        let backend = Backend::new();
+        run(&backend, &["SET", "k", "10.50"])?;
+        assert_eq!(run(&backend, &["INCRBYFLOAT", "k", "0.1"])?, b"10.6".into());
+        assert_eq!(run(&backend, &["INCRBYFLOAT", "k", "-5"])?, b"5.6".into());
+        run(&backend, &["SET", "k", "5.0e3"])?;
+        assert_eq!(
+            run(&backend, &["INCRBYFLOAT", "k", "2.0e2"])?,
+            b"5200".into()
+        );
+        assert_eq!(run(&backend, &["INCR", "k"])?, 5201.into());
+        assert_eq!(
+            run(&backend, &["INCRBYFLOAT", "new", "1e3"])?,
+            b"1000".into()
+        );
+
+        // the sum of the decimals, not of their binary approximations
+        run(&backend, &["SET", "k", "0.1"])?;
+        assert_eq!(run(&backend, &["INCRBYFLOAT", "k", "0.2"])?, b"0.3".into());
+        assert_eq!(run(&backend, &["INCRBYFLOAT", "k", "-0.3"])?, b"0".into());
+        // rounded to 17 decimal places, `-0` is printed as `0`
+        assert_eq!(
+            run(&backend, &["INCRBYFLOAT", "k", "-1.5e-20"])?,
+            b"0".into()
+        );
+        assert_eq!(
+            run(&backend, &["INCRBYFLOAT", "k", "-2.4e-17"])?,
+            b"-0.00000000000000002".into()
+        );
+        assert_eq!(
+            run(&backend, &["INCRBYFLOAT", "k", "1e20"])?,
+            b"100000000000000000000".into()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_incrbyfloat_errors() -> Result<()> {
+        let backend = Backend::new();
+        run(&backend, &["SET", "k", "abc"])?;
+        let err = run(&backend, &["INCRBYFLOAT", "k", "1"]).unwrap_err();
+        assert_eq!(err.to_string(), "ERR value is not a valid float");
+        assert_eq!(
+            run(&backend, &["INCRBYFLOAT", "n", "nan"]),
+            Err(CommandError::NotFloat)
+        );
+        assert_eq!(
+            run(&backend, &["INCRBYFLOAT", "n", " 1"]),
+            Err(CommandError::NotFloat)
+        );
+        let err = run(&backend, &["INCRBYFLOAT", "n", "inf"]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "ERR increment would produce NaN or Infinity"
+        );
+        assert_eq!(run(&backend, &["GET", "n"])?, BulkNullString.into());
+        Ok(())
+    }
+}
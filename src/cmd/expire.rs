@@ -0,0 +1,510 @@
+use bytes::Bytes;
+
+use crate::{
+    cmd::{fixed_args, parse_integer, CommandError, CommandExecutor, CommandSpec},
+    Backend, RespFrame,
+};
+
+/// `EXPIRE key seconds [NX | XX | GT | LT]`
+#[derive(Debug, PartialEq)]
+pub struct Expire {
+    key: Bytes,
+    seconds: i64,
+    condition: ExpireCondition,
+}
+
+/// `PEXPIRE key milliseconds [NX | XX | GT | LT]`
+#[derive(Debug, PartialEq)]
+pub struct PExpire {
+    key: Bytes,
+    milliseconds: i64,
+    condition: ExpireCondition,
+}
+
+/// `EXPIREAT key unix-time-seconds [NX | XX | GT | LT]`
+#[derive(Debug, PartialEq)]
+pub struct ExpireAt {
+    key: Bytes,
+    unix_time_seconds: i64,
+    condition: ExpireCondition,
+}
+
+/// `PEXPIREAT key unix-time-milliseconds [NX | XX | GT | LT]`
+#[derive(Debug, PartialEq)]
+pub struct PExpireAt {
+    key: Bytes,
+    unix_time_milliseconds: i64,
+    condition: ExpireCondition,
+}
+
+/// `TTL key`
+#[derive(Debug, PartialEq)]
+pub struct Ttl {
+    key: Bytes,
+}
+
+/// `PTTL key`
+#[derive(Debug, PartialEq)]
+pub struct PTtl {
+    key: Bytes,
+}
+
+/// `EXPIRETIME key`
+#[derive(Debug, PartialEq)]
+pub struct ExpireTime {
+    key: Bytes,
+}
+
+/// `PEXPIRETIME key`
+#[derive(Debug, PartialEq)]
+pub struct PExpireTime {
+    key: Bytes,
+}
+
+/// `PERSIST key`
+#[derive(Debug, PartialEq)]
+pub struct Persist {
+    key: Bytes,
+}
+
+/// When the `EXPIRE` family may change the ttl. Unlike `NX`, the other options combine.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct ExpireCondition {
+    /// Only if the key has no ttl.
+    nx: bool,
+    /// Only if the key has a ttl.
+    xx: bool,
+    /// Only if the new ttl is greater, no ttl counting as infinite.
+    gt: bool,
+    /// Only if the new ttl is less, no ttl counting as infinite.
+    lt: bool,
+}
+
+impl ExpireCondition {
+    fn parse(args: impl Iterator<Item = Bytes>) -> Result<Self, CommandError> {
+        let mut condition = ExpireCondition::default();
+        for arg in args {
+            match arg.to_ascii_uppercase().as_slice() {
+                b"NX" => condition.nx = true,
+                b"XX" => condition.xx = true,
+                b"GT" => condition.gt = true,
+                b"LT" => condition.lt = true,
+                _ => {
+                    return Err(CommandError::UnsupportedOption(
+                        String::from_utf8_lossy(&arg).into_owned(),
+                    ))
+                }
+            }
+        }
+        if condition.nx && (condition.xx || condition.gt || condition.lt) {
+            return Err(CommandError::InvalidArgument(
+                "NX and XX, GT or LT options at the same time are not compatible",
+            ));
+        }
+        if condition.gt && condition.lt {
+            return Err(CommandError::InvalidArgument(
+                "GT and LT options at the same time are not compatible",
+            ));
+        }
+        Ok(condition)
+    }
+
+    // `current` and `new` are unix milliseconds
+    fn allows(self, current: Option<u64>, new: i64) -> bool {
+        let current = current.map(|at| at as i64);
+        !(self.nx && current.is_some()
+            || self.xx && current.is_none()
+            || self.gt && current.is_none_or(|current| new <= current)
+            || self.lt && current.is_some_and(|current| new >= current))
+    }
+}
+
+// the key, the time and the options every command of the `EXPIRE` family takes
+fn expire_args(
+    name: &'static str,
+    args: Vec<Bytes>,
+) -> Result<(Bytes, i64, ExpireCondition), CommandError> {
+    let mut args = args.into_iter();
+    let (Some(key), Some(when)) = (args.next(), args.next()) else {
+        return Err(CommandError::WrongArity(name));
+    };
+    // like redis, the options are checked before the time
+    let condition = ExpireCondition::parse(args)?;
+    Ok((key, parse_integer(&when)?, condition))
+}
+
+impl CommandSpec for Expire {
+    const NAME: &'static str = "expire";
+    const ARITY: isize = -3;
+
+    fn parse(args: Vec<Bytes>) -> Result<Self, CommandError> {
+        let (key, seconds, condition) = expire_args(Self::NAME, args)?;
+        Ok(Expire {
+            key,
+            seconds,
+            condition,
+        })
+    }
+}
+
+impl CommandSpec for PExpire {
+    const NAME: &'static str = "pexpire";
+    const ARITY: isize = -3;
+
+    fn parse(args: Vec<Bytes>) -> Result<Self, CommandError> {
+        let (key, milliseconds, condition) = expire_args(Self::NAME, args)?;
+        Ok(PExpire {
+            key,
+            milliseconds,
+            condition,
+        })
+    }
+}
+
+impl CommandSpec for ExpireAt {
+    const NAME: &'static str = "expireat";
+    const ARITY: isize = -3;
+
+    fn parse(args: Vec<Bytes>) -> Result<Self, CommandError> {
+        let (key, unix_time_seconds, condition) = expire_args(Self::NAME, args)?;
+        Ok(ExpireAt {
+            key,
+            unix_time_seconds,
+            condition,
+        })
+    }
+}
+
+impl CommandSpec for PExpireAt {
+    const NAME: &'static str = "pexpireat";
+    const ARITY: isize = -3;
+
+    fn parse(args: Vec<Bytes>) -> Result<Self, CommandError> {
+        let (key, unix_time_milliseconds, condition) = expire_args(Self::NAME, args)?;
+        Ok(PExpireAt {
+            key,
+            unix_time_milliseconds,
+            condition,
+        })
+    }
+}
+
+impl CommandSpec for Ttl {
+    const NAME: &'static str = "ttl";
+    const ARITY: isize = 2;
+
+    fn parse(args: Vec<Bytes>) -> Result<Self, CommandError> {
+        let [key] = fixed_args(Self::NAME, args)?;
+        Ok(Ttl { key })
+    }
+}
+
+impl CommandSpec for PTtl {
+    const NAME: &'static str = "pttl";
+    const ARITY: isize = 2;
+
+    fn parse(args: Vec<Bytes>) -> Result<Self, CommandError> {
+        let [key] = fixed_args(Self::NAME, args)?;
+        Ok(PTtl { key })
+    }
+}
+
+impl CommandSpec for ExpireTime {
+    const NAME: &'static str = "expiretime";
+    const ARITY: isize = 2;
+
+    fn parse(args: Vec<Bytes>) -> Result<Self, CommandError> {
+        let [key] = fixed_args(Self::NAME, args)?;
+        Ok(ExpireTime { key })
+    }
+}
+
+impl CommandSpec for PExpireTime {
+    const NAME: &'static str = "pexpiretime";
+    const ARITY: isize = 2;
+
+    fn parse(args: Vec<Bytes>) -> Result<Self, CommandError> {
+        let [key] = fixed_args(Self::NAME, args)?;
+        Ok(PExpireTime { key })
+    }
+}
+
+impl CommandSpec for Persist {
+    const NAME: &'static str = "persist";
+    const ARITY: isize = 2;
+
+    fn parse(args: Vec<Bytes>) -> Result<Self, CommandError> {
+        let [key] = fixed_args(Self::NAME, args)?;
+        Ok(Persist { key })
+    }
+}
+
+impl CommandExecutor for Expire {
+    fn execute(self, backend: &Backend) -> Result<RespFrame, CommandError> {
+        let when = When::After(self.seconds, 1000);
+        expire(backend, Self::NAME, self.key, when, self.condition)
+    }
+}
+
+impl CommandExecutor for PExpire {
+    fn execute(self, backend: &Backend) -> Result<RespFrame, CommandError> {
+        let when = When::After(self.milliseconds, 1);
+        expire(backend, Self::NAME, self.key, when, self.condition)
+    }
+}
+
+impl CommandExecutor for ExpireAt {
+    fn execute(self, backend: &Backend) -> Result<RespFrame, CommandError> {
+        let when = When::At(self.unix_time_seconds, 1000);
+        expire(backend, Self::NAME, self.key, when, self.condition)
+    }
+}
+
+impl CommandExecutor for PExpireAt {
+    fn execute(self, backend: &Backend) -> Result<RespFrame, CommandError> {
+        let when = When::At(self.unix_time_milliseconds, 1);
+        expire(backend, Self::NAME, self.key, when, self.condition)
+    }
+}
+
+impl CommandExecutor for Ttl {
+    fn execute(self, backend: &Backend) -> Result<RespFrame, CommandError> {
+        Ok(ttl(backend, &self.key, false, false))
+    }
+}
+
+impl CommandExecutor for PTtl {
+    fn execute(self, backend: &Backend) -> Result<RespFrame, CommandError> {
+        Ok(ttl(backend, &self.key, true, false))
+    }
+}
+
+impl CommandExecutor for ExpireTime {
+    fn execute(self, backend: &Backend) -> Result<RespFrame, CommandError> {
+        Ok(ttl(backend, &self.key, false, true))
+    }
+}
+
+impl CommandExecutor for PExpireTime {
+    fn execute(self, backend: &Backend) -> Result<RespFrame, CommandError> {
+        Ok(ttl(backend, &self.key, true, true))
+    }
+}
+
+impl CommandExecutor for Persist {
+    fn execute(self, backend: &Backend) -> Result<RespFrame, CommandError> {
+        let persisted = backend.write(|keyspace, now| {
+            let has_ttl = keyspace
+                .get(&self.key, now)
+                .is_some_and(|entry| entry.expires_at.is_some());
+            has_ttl && keyspace.set_expiry(&self.key, None, now)
+        });
+        Ok(RespFrame::from(persisted as i64))
+    }
+}
+
+/// A time from the `EXPIRE` family, in units of the given number of milliseconds.
+#[derive(Debug, Clone, Copy)]
+enum When {
+    After(i64, i64),
+    At(i64, i64),
+}
+
+impl When {
+    // the unix milliseconds it stands for, which may be in the past or even negative
+    fn resolve(self, name: &'static str, now: u64) -> Result<i64, CommandError> {
+        let (value, unit, base) = match self {
+            When::After(value, unit) => (value, unit, now as i64),
+            When::At(value, unit) => (value, unit, 0),
+        };
+        value
+            .checked_mul(unit)
+            .and_then(|ms| ms.checked_add(base))
+            .ok_or(CommandError::InvalidExpireTime(name))
+    }
+}
+
+// sets the ttl of `key` if `condition` allows it, deleting it right away if the time has
+// passed, and replies whether anything changed
+fn expire(
+    backend: &Backend,
+    name: &'static str,
+    key: Bytes,
+    when: When,
+    condition: ExpireCondition,
+) -> Result<RespFrame, CommandError> {
+    backend.write(|keyspace, now| {
+        let expires_at = when.resolve(name, now)?;
+        let Some(entry) = keyspace.get(&key, now) else {
+            return Ok(RespFrame::from(0));
+        };
+        if !condition.allows(entry.expires_at, expires_at) {
+            return Ok(RespFrame::from(0));
+        }
+        if expires_at <= now as i64 {
+            keyspace.remove(&key, now);
+        } else {
+            keyspace.set_expiry(&key, Some(expires_at as u64), now);
+        }
+        Ok(RespFrame::from(1))
+    })
+}
+
+// what `TTL`, `PTTL`, `EXPIRETIME` and `PEXPIRETIME` reply: -2 without the key, -1 without a
+// ttl, and otherwise the remaining or absolute time, seconds being rounded like redis does
+fn ttl(backend: &Backend, key: &[u8], ms: bool, absolute: bool) -> RespFrame {
+    let reply = backend.read(|keyspace, now| {
+        let Some(entry) = keyspace.get(key, now) else {
+            return -2;
+        };
+        let Some(expires_at) = entry.expires_at else {
+            return -1;
+        };
+        let time = match absolute {
+            true => expires_at,
+            false => expires_at.saturating_sub(now),
+        } as i64;
+        match ms {
+            true => time,
+            false => (time + 500) / 1000,
+        }
+    });
+    reply.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{backend::ManualClock, cmd::tests::run, BulkNullString};
+    use anyhow::Result;
+
+    // an arbitrary, round point in time
+    const NOW: u64 = 1_700_000_000_000;
+
+    fn setup() -> Result<(Backend, ManualClock)> {
+        let clock = ManualClock::new(NOW);
+        let backend = Backend::with_clock(clock.clone());
+        run(&backend, &["SET", "k", "v"])?;
+        Ok((backend, clock))
+    }
+
+    #[test]
+    fn test_expire_and_ttl() -> Result<()> {
+        let (backend, clock) = setup()?;
+        assert_eq!(run(&backend, &["TTL", "k"])?, (-1).into());
+        assert_eq!(run(&backend, &["PTTL", "missing"])?, (-2).into());
+        assert_eq!(run(&backend, &["EXPIRE", "missing", "10"])?, 0.into());
+
+        assert_eq!(run(&backend, &["EXPIRE", "k", "10"])?, 1.into());
+        assert_eq!(run(&backend, &["TTL", "k"])?, 10.into());
+        assert_eq!(run(&backend, &["PTTL", "k"])?, 10_000.into());
+        let expected = (NOW / 1000 + 10) as i64;
+        assert_eq!(run(&backend, &["EXPIRETIME", "k"])?, expected.into());
+        let expected = (NOW + 10_000) as i64;
+        assert_eq!(run(&backend, &["PEXPIRETIME", "k"])?, expected.into());
+
+        // seconds are rounded
+        clock.advance(9_400);
+        assert_eq!(run(&backend, &["TTL", "k"])?, 1.into());
+        assert_eq!(run(&backend, &["PTTL", "k"])?, 600.into());
+        clock.advance(600);
+        assert_eq!(run(&backend, &["TTL", "k"])?, (-2).into());
+        assert_eq!(run(&backend, &["GET", "k"])?, BulkNullString.into());
+        Ok(())
+    }
+
+    #[test]
+    fn test_expire_variants() -> Result<()> {
+        let (backend, _) = setup()?;
+        run(&backend, &["PEXPIRE", "k", "1500"])?;
+        assert_eq!(run(&backend, &["PTTL", "k"])?, 1500.into());
+
+        let at = (NOW / 1000 + 100).to_string();
+        run(&backend, &["EXPIREAT", "k", &at])?;
+        assert_eq!(run(&backend, &["TTL", "k"])?, 100.into());
+
+        let at = (NOW + 42).to_string();
+        run(&backend, &["PEXPIREAT", "k", &at])?;
+        assert_eq!(run(&backend, &["PTTL", "k"])?, 42.into());
+        Ok(())
+    }
+
+    #[test]
+    fn test_expire_in_the_past_deletes() -> Result<()> {
+        let (backend, _) = setup()?;
+        assert_eq!(run(&backend, &["EXPIRE", "k", "-1"])?, 1.into());
+        assert_eq!(run(&backend, &["TTL", "k"])?, (-2).into());
+
+        run(&backend, &["SET", "k", "v"])?;
+        assert_eq!(
+            run(&backend, &["PEXPIREAT", "k", &NOW.to_string()])?,
+            1.into()
+        );
+        assert_eq!(run(&backend, &["GET", "k"])?, BulkNullString.into());
+        Ok(())
+    }
+
+    #[test]
+    fn test_expire_conditions() -> Result<()> {
+        let (backend, _) = setup()?;
+        assert_eq!(run(&backend, &["EXPIRE", "k", "10", "XX"])?, 0.into());
+        assert_eq!(run(&backend, &["EXPIRE", "k", "10", "GT"])?, 0.into());
+        assert_eq!(run(&backend, &["EXPIRE", "k", "10", "NX"])?, 1.into());
+        assert_eq!(run(&backend, &["EXPIRE", "k", "20", "NX"])?, 0.into());
+
+        assert_eq!(run(&backend, &["EXPIRE", "k", "5", "gt"])?, 0.into());
+        assert_eq!(run(&backend, &["EXPIRE", "k", "20", "GT"])?, 1.into());
+        assert_eq!(run(&backend, &["EXPIRE", "k", "30", "LT"])?, 0.into());
+        assert_eq!(run(&backend, &["EXPIRE", "k", "15", "XX", "LT"])?, 1.into());
+        assert_eq!(run(&backend, &["TTL", "k"])?, 15.into());
+
+        run(&backend, &["PERSIST", "k"])?;
+        assert_eq!(run(&backend, &["EXPIRE", "k", "15", "LT"])?, 1.into());
+        Ok(())
+    }
+
+    #[test]
+    fn test_expire_errors() -> Result<()> {
+        let (backend, _) = setup()?;
+        let err = run(&backend, &["EXPIRE", "k", "10", "NX", "GT"]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "ERR NX and XX, GT or LT options at the same time are not compatible"
+        );
+        let err = run(&backend, &["EXPIRE", "k", "10", "GT", "LT"]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "ERR GT and LT options at the same time are not compatible"
+        );
+        let err = run(&backend, &["EXPIRE", "k", "10", "foo"]).unwrap_err();
+        assert_eq!(err.to_string(), "ERR Unsupported option foo");
+        assert_eq!(
+            run(&backend, &["EXPIRE", "k", "ten"]),
+            Err(CommandError::NotInteger)
+        );
+        let err = run(&backend, &["EXPIRE", "k", "9223372036854775807"]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "ERR invalid expire time in 'expire' command"
+        );
+        assert_eq!(
+            run(&backend, &["PEXPIRE", "missing", "9223372036854775807"]),
+            Err(CommandError::InvalidExpireTime("pexpire"))
+        );
+        assert_eq!(run(&backend, &["TTL", "k"])?, (-1).into());
+        Ok(())
+    }
+
+    #[test]
+    fn test_persist() -> Result<()> {
+        let (backend, clock) = setup()?;
+        assert_eq!(run(&backend, &["PERSIST", "k"])?, 0.into());
+        assert_eq!(run(&backend, &["PERSIST", "missing"])?, 0.into());
+        run(&backend, &["EXPIRE", "k", "10"])?;
+        assert_eq!(run(&backend, &["PERSIST", "k"])?, 1.into());
+        assert_eq!(run(&backend, &["TTL", "k"])?, (-1).into());
+        clock.advance(20_000);
+        assert_eq!(run(&backend, &["GET", "k"])?, b"v".into());
+        Ok(())
+    }
+}
@@ -0,0 +1,329 @@
+use bytes::Bytes;
+use enum_dispatch::enum_dispatch;
+use thiserror::Error;
+
+use crate::{Backend, RespFrame, RespFrameArray, SimpleError};
+
+pub use connection::{Echo, Ping};
+pub use counter::{Decr, DecrBy, Incr, IncrBy, IncrByFloat};
+pub use expire::{
+    Expire, ExpireAt, ExpireTime, PExpire, PExpireAt, PExpireTime, PTtl, Persist, Ttl,
+};
+pub use string::{
+    Append, Get, GetDel, GetEx, GetRange, GetSet, Lcs, MGet, MSet, MSetNx, Set, SetRange, StrLen,
+};
+
+mod connection;
+mod counter;
+mod expire;
+mod string;
+
+#[enum_dispatch]
+pub trait CommandExecutor {
+    fn execute(self, backend: &Backend) -> Result<RespFrame, CommandError>;
+}
+
+/// A request parsed from the array of bulk strings a client sends.
+#[enum_dispatch(CommandExecutor)]
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Ping(Ping),
+    Echo(Echo),
+    Get(Get),
+    Set(Set),
+    GetSet(GetSet),
+    GetDel(GetDel),
+    GetEx(GetEx),
+    MGet(MGet),
+    MSet(MSet),
+    MSetNx(MSetNx),
+    Append(Append),
+    StrLen(StrLen),
+    SetRange(SetRange),
+    GetRange(GetRange),
+    Lcs(Lcs),
+    Incr(Incr),
+    Decr(Decr),
+    IncrBy(IncrBy),
+    DecrBy(DecrBy),
+    IncrByFloat(IncrByFloat),
+    Expire(Expire),
+    PExpire(PExpire),
+    ExpireAt(ExpireAt),
+    PExpireAt(PExpireAt),
+    Ttl(Ttl),
+    PTtl(PTtl),
+    ExpireTime(ExpireTime),
+    PExpireTime(PExpireTime),
+    Persist(Persist),
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum CommandError {
+    #[error("ERR Protocol error: {0}")]
+    InvalidRequest(String),
+    #[error("ERR unknown command '{name}', with args beginning with: {args}")]
+    UnknownCommand { name: String, args: String },
+    #[error("ERR wrong number of arguments for '{0}' command")]
+    WrongArity(&'static str),
+    #[error("ERR syntax error")]
+    SyntaxError,
+    #[error("ERR value is not an integer or out of range")]
+    NotInteger,
+    #[error("ERR value is not a valid float")]
+    NotFloat,
+    #[error("ERR increment or decrement would overflow")]
+    Overflow,
+    #[error("ERR invalid expire time in '{0}' command")]
+    InvalidExpireTime(&'static str),
+    #[error("ERR offset is out of range")]
+    OffsetOutOfRange,
+    #[error("ERR string exceeds maximum allowed size (proto-max-bulk-len)")]
+    StringTooLong,
+    #[error("ERR Unsupported option {0}")]
+    UnsupportedOption(String),
+    #[error("ERR {0}")]
+    InvalidArgument(&'static str),
+}
+
+/// How a command is looked up and parsed from its arguments.
+trait CommandSpec: Sized {
+    /// The lowercase name of the command.
+    const NAME: &'static str;
+    /// The number of arguments including the name like redis counts them: exactly `ARITY`
+    /// if positive, at least `-ARITY` if negative.
+    const ARITY: isize;
+
+    /// Parses the arguments following the name, their number is already checked.
+    fn parse(args: Vec<Bytes>) -> Result<Self, CommandError>;
+}
+
+impl From<CommandError> for RespFrame {
+    fn from(e: CommandError) -> Self {
+        error_reply(e.to_string())
+    }
+}
+
+/// An error reply with `message` on one line. Like redis' `addReplyErrorFormat`, line breaks
+/// are replaced with spaces, as messages may quote what the client sent.
+pub fn error_reply(message: String) -> RespFrame {
+    SimpleError::new(message.replace(['\r', '\n'], " ")).into()
+}
+
+impl TryFrom<RespFrame> for Command {
+    type Error = CommandError;
+    fn try_from(frame: RespFrame) -> Result<Self, Self::Error> {
+        match frame {
+            RespFrame::Array(args) => args.try_into(),
+            _ => Err(CommandError::InvalidRequest(
+                "expected an array of bulk strings".to_string(),
+            )),
+        }
+    }
+}
+
+impl TryFrom<RespFrameArray> for Command {
+    type Error = CommandError;
+    fn try_from(frames: RespFrameArray) -> Result<Self, Self::Error> {
+        let mut args = frames
+            .0
+            .into_iter()
+            .map(|frame| match frame {
+                RespFrame::BulkStrings(s) => Ok(s.0),
+                _ => Err(CommandError::InvalidRequest(
+                    "expected an array of bulk strings".to_string(),
+                )),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if args.is_empty() {
+            return Err(CommandError::InvalidRequest("empty request".to_string()));
+        }
+
+        let name = args.remove(0);
+        match name.to_ascii_lowercase().as_slice() {
+            b"ping" => parse::<Ping>(args),
+            b"echo" => parse::<Echo>(args),
+            b"get" => parse::<Get>(args),
+            b"set" => parse::<Set>(args),
+            b"getset" => parse::<GetSet>(args),
+            b"getdel" => parse::<GetDel>(args),
+            b"getex" => parse::<GetEx>(args),
+            b"mget" => parse::<MGet>(args),
+            b"mset" => parse::<MSet>(args),
+            b"msetnx" => parse::<MSetNx>(args),
+            b"append" => parse::<Append>(args),
+            b"strlen" => parse::<StrLen>(args),
+            b"setrange" => parse::<SetRange>(args),
+            b"getrange" => parse::<GetRange>(args),
+            b"lcs" => parse::<Lcs>(args),
+            b"incr" => parse::<Incr>(args),
+            b"decr" => parse::<Decr>(args),
+            b"incrby" => parse::<IncrBy>(args),
+            b"decrby" => parse::<DecrBy>(args),
+            b"incrbyfloat" => parse::<IncrByFloat>(args),
+            b"expire" => parse::<Expire>(args),
+            b"pexpire" => parse::<PExpire>(args),
+            b"expireat" => parse::<ExpireAt>(args),
+            b"pexpireat" => parse::<PExpireAt>(args),
+            b"ttl" => parse::<Ttl>(args),
+            b"pttl" => parse::<PTtl>(args),
+            b"expiretime" => parse::<ExpireTime>(args),
+            b"pexpiretime" => parse::<PExpireTime>(args),
+            b"persist" => parse::<Persist>(args),
+            _ => Err(unknown_command(&name, &args)),
+        }
+    }
+}
+
+fn parse<C: CommandSpec + Into<Command>>(args: Vec<Bytes>) -> Result<Command, CommandError> {
+    // `args` doesn't include the name
+    let argc = args.len() + 1;
+    let valid = match C::ARITY {
+        arity if arity >= 0 => argc == arity as usize,
+        arity => argc >= arity.unsigned_abs(),
+    };
+    if !valid {
+        return Err(CommandError::WrongArity(C::NAME));
+    }
+    C::parse(args).map(Into::into)
+}
+
+// the arity is checked before parsing, so this only fails for commands with a variable arity
+fn fixed_args<const N: usize>(
+    name: &'static str,
+    args: Vec<Bytes>,
+) -> Result<[Bytes; N], CommandError> {
+    args.try_into().map_err(|_| CommandError::WrongArity(name))
+}
+
+// as strict as redis' `string2ll`: no sign but `-`, no leading zeros and no whitespace
+fn parse_integer(arg: &[u8]) -> Result<i64, CommandError> {
+    let canonical = match arg.strip_prefix(b"-").unwrap_or(arg) {
+        [b'0'] => arg.len() == 1,
+        [b'1'..=b'9', rest @ ..] => rest.iter().all(u8::is_ascii_digit),
+        _ => false,
+    };
+    if !canonical {
+        return Err(CommandError::NotInteger);
+    }
+    std::str::from_utf8(arg)
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .ok_or(CommandError::NotInteger)
+}
+
+// the same message redis replies with, quoting the first few arguments
+fn unknown_command(name: &[u8], args: &[Bytes]) -> CommandError {
+    let args = args
+        .iter()
+        .take(3)
+        .map(|arg| format!("'{}' ", String::from_utf8_lossy(arg)))
+        .collect();
+    CommandError::UnknownCommand {
+        name: String::from_utf8_lossy(name).into_owned(),
+        args,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BulkString, RespEncode, RespFrameArray};
+    use anyhow::Result;
+
+    pub(super) fn request(args: &[&str]) -> RespFrame {
+        let args: Vec<RespFrame> = args
+            .iter()
+            .map(|arg| BulkString::new(*arg).into())
+            .collect();
+        RespFrameArray::new(args).into()
+    }
+
+    pub(super) fn run(backend: &Backend, args: &[&str]) -> Result<RespFrame, CommandError> {
+        Command::try_from(request(args))?.execute(backend)
+    }
+
+    #[test]
+    fn test_command_names_are_case_insensitive() -> Result<()> {
+        for name in ["ping", "PING", "PiNg"] {
+            assert_eq!(Command::try_from(request(&[name]))?, Ping::new().into());
+        }
+        assert_eq!(
+            Command::try_from(request(&["ECHO", "hello"]))?,
+            Echo::new("hello").into()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_command_wrong_arity() {
+        let err = Command::try_from(request(&["echo"])).unwrap_err();
+        assert_eq!(err, CommandError::WrongArity("echo"));
+        assert_eq!(
+            RespFrame::from(err),
+            SimpleError::new("ERR wrong number of arguments for 'echo' command").into()
+        );
+
+        let err = Command::try_from(request(&["ping", "a", "b"])).unwrap_err();
+        assert_eq!(err, CommandError::WrongArity("ping"));
+    }
+
+    #[test]
+    fn test_parse_integer() {
+        assert_eq!(parse_integer(b"0"), Ok(0));
+        assert_eq!(parse_integer(b"-42"), Ok(-42));
+        assert_eq!(parse_integer(b"-9223372036854775808"), Ok(i64::MIN));
+        for arg in [
+            "",
+            "-",
+            "-0",
+            "+1",
+            "01",
+            " 1",
+            "1 ",
+            "1.0",
+            "9223372036854775808",
+        ] {
+            assert_eq!(parse_integer(arg.as_bytes()), Err(CommandError::NotInteger));
+        }
+    }
+
+    #[test]
+    fn test_command_unknown() {
+        let err = Command::try_from(request(&["foo", "a", "b"])).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "ERR unknown command 'foo', with args beginning with: 'a' 'b' "
+        );
+
+        let err = Command::try_from(request(&["foo"])).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "ERR unknown command 'foo', with args beginning with: "
+        );
+
+        // the reply quotes the request, which must not end the reply line early
+        let err = Command::try_from(request(&["foo\r\n+OK", "a\nb"])).unwrap_err();
+        assert_eq!(
+            RespFrame::from(err).encode(),
+            b"-ERR unknown command 'foo  +OK', with args beginning with: 'a b' \r\n"
+        );
+    }
+
+    #[test]
+    fn test_command_invalid_request() {
+        let frame: RespFrame = RespFrameArray::new(vec![b"ping".into(), 1.into()]).into();
+        assert!(matches!(
+            Command::try_from(frame),
+            Err(CommandError::InvalidRequest(_))
+        ));
+        assert!(matches!(
+            Command::try_from(RespFrame::from(1)),
+            Err(CommandError::InvalidRequest(_))
+        ));
+        assert!(matches!(
+            Command::try_from(request(&[])),
+            Err(CommandError::InvalidRequest(_))
+        ));
+    }
+}
@@ -0,0 +1,1093 @@
+use bytes::Bytes;
+
+use crate::{
+    backend::Entry,
+    cmd::{fixed_args, parse_integer, CommandError, CommandExecutor, CommandSpec},
+    Backend, BulkNullString, BulkString, RespFrame, RespFrameArray, RespMap, SimpleString,
+};
+
+// redis' default `proto-max-bulk-len`
+const MAX_STRING_LEN: usize = 512 * 1024 * 1024;
+
+/// `GET key`
+#[derive(Debug, PartialEq)]
+pub struct Get {
+    key: Bytes,
+}
+
+/// `SET key value [NX | XX] [GET] [EX seconds | PX milliseconds | EXAT unix-time-seconds |
+/// PXAT unix-time-milliseconds | KEEPTTL]`
+#[derive(Debug, PartialEq)]
+pub struct Set {
+    key: Bytes,
+    value: Bytes,
+    condition: Option<SetCondition>,
+    get: bool,
+    expiry: Option<Expiry>,
+}
+
+/// `GETSET key value`
+#[derive(Debug, PartialEq)]
+pub struct GetSet {
+    key: Bytes,
+    value: Bytes,
+}
+
+/// `GETDEL key`
+#[derive(Debug, PartialEq)]
+pub struct GetDel {
+    key: Bytes,
+}
+
+/// `GETEX key [EX seconds | PX milliseconds | EXAT unix-time-seconds |
+/// PXAT unix-time-milliseconds | PERSIST]`
+#[derive(Debug, PartialEq)]
+pub struct GetEx {
+    key: Bytes,
+    expiry: Option<Expiry>,
+}
+
+/// `MGET key [key ...]`
+#[derive(Debug, PartialEq)]
+pub struct MGet {
+    keys: Vec<Bytes>,
+}
+
+/// `MSET key value [key value ...]`
+#[derive(Debug, PartialEq)]
+pub struct MSet {
+    pairs: Vec<(Bytes, Bytes)>,
+}
+
+/// `MSETNX key value [key value ...]`
+#[derive(Debug, PartialEq)]
+pub struct MSetNx {
+    pairs: Vec<(Bytes, Bytes)>,
+}
+
+/// `APPEND key value`
+#[derive(Debug, PartialEq)]
+pub struct Append {
+    key: Bytes,
+    value: Bytes,
+}
+
+/// `STRLEN key`
+#[derive(Debug, PartialEq)]
+pub struct StrLen {
+    key: Bytes,
+}
+
+/// `SETRANGE key offset value`
+#[derive(Debug, PartialEq)]
+pub struct SetRange {
+    key: Bytes,
+    offset: usize,
+    value: Bytes,
+}
+
+/// `GETRANGE key start end`
+#[derive(Debug, PartialEq)]
+pub struct GetRange {
+    key: Bytes,
+    start: i64,
+    end: i64,
+}
+
+/// `LCS key1 key2 [LEN] [IDX] [MINMATCHLEN min-match-len] [WITHMATCHLEN]`
+#[derive(Debug, PartialEq)]
+pub struct Lcs {
+    key1: Bytes,
+    key2: Bytes,
+    len: bool,
+    idx: bool,
+    min_match_len: usize,
+    with_match_len: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SetCondition {
+    Nx,
+    Xx,
+}
+
+/// How `SET` and `GETEX` change the expiry of a key.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Expiry {
+    /// Milliseconds from now, from `EX` or `PX`.
+    After(u64),
+    /// Unix milliseconds, from `EXAT` or `PXAT`.
+    At(u64),
+    KeepTtl,
+    Persist,
+}
+
+impl Expiry {
+    // parses the `EX`, `PX`, `EXAT` or `PXAT` option named `option`, taking its argument
+    fn parse(
+        name: &'static str,
+        option: &[u8],
+        args: &mut impl Iterator<Item = Bytes>,
+    ) -> Result<Option<Self>, CommandError> {
+        let (absolute, unit) = match option.to_ascii_uppercase().as_slice() {
+            b"EX" => (false, 1000),
+            b"PX" => (false, 1),
+            b"EXAT" => (true, 1000),
+            b"PXAT" => (true, 1),
+            _ => return Ok(None),
+        };
+        let value = parse_integer(&args.next().ok_or(CommandError::SyntaxError)?)?;
+        let ms = u64::try_from(value)
+            .ok()
+            .filter(|&value| value > 0)
+            .and_then(|value| value.checked_mul(unit))
+            .ok_or(CommandError::InvalidExpireTime(name))?;
+        Ok(Some(if absolute {
+            Expiry::At(ms)
+        } else {
+            Expiry::After(ms)
+        }))
+    }
+
+    // the new expiry of a key which currently expires at `current`
+    fn resolve(
+        self,
+        name: &'static str,
+        current: Option<u64>,
+        now: u64,
+    ) -> Result<Option<u64>, CommandError> {
+        match self {
+            Expiry::After(ms) => now
+                .checked_add(ms)
+                .map(Some)
+                .ok_or(CommandError::InvalidExpireTime(name)),
+            Expiry::At(ms) => Ok(Some(ms)),
+            Expiry::KeepTtl => Ok(current),
+            Expiry::Persist => Ok(None),
+        }
+    }
+}
+
+fn bulk_or_nil(value: Option<Bytes>) -> RespFrame {
+    match value {
+        Some(value) => BulkString::from(value).into(),
+        None => BulkNullString.into(),
+    }
+}
+
+// Arguments are views into the connection's read buffer, a stored copy lets it be freed.
+fn stored(value: &[u8]) -> Bytes {
+    Bytes::copy_from_slice(value)
+}
+
+fn ok() -> RespFrame {
+    SimpleString::new("OK").into()
+}
+
+fn into_pairs(name: &'static str, args: Vec<Bytes>) -> Result<Vec<(Bytes, Bytes)>, CommandError> {
+    if !args.len().is_multiple_of(2) {
+        return Err(CommandError::WrongArity(name));
+    }
+    let mut pairs = Vec::with_capacity(args.len() / 2);
+    let mut args = args.into_iter();
+    while let (Some(key), Some(value)) = (args.next(), args.next()) {
+        pairs.push((key, value));
+    }
+    Ok(pairs)
+}
+
+// Like redis, an expiry option may be repeated, the last one winning, but two different ones
+// conflict. `option` is the name of the one just parsed, `current` of those before it.
+fn check_expiry_option(current: &mut Option<Vec<u8>>, option: &[u8]) -> Result<(), CommandError> {
+    let option = option.to_ascii_uppercase();
+    if current.as_ref().is_some_and(|current| *current != option) {
+        return Err(CommandError::SyntaxError);
+    }
+    *current = Some(option);
+    Ok(())
+}
+
+fn check_string_len(len: usize) -> Result<(), CommandError> {
+    if len > MAX_STRING_LEN {
+        return Err(CommandError::StringTooLong);
+    }
+    Ok(())
+}
+
+impl CommandSpec for Get {
+    const NAME: &'static str = "get";
+    const ARITY: isize = 2;
+
+    fn parse(args: Vec<Bytes>) -> Result<Self, CommandError> {
+        let [key] = fixed_args(Self::NAME, args)?;
+        Ok(Get { key })
+    }
+}
+
+impl CommandSpec for Set {
+    const NAME: &'static str = "set";
+    const ARITY: isize = -3;
+
+    fn parse(args: Vec<Bytes>) -> Result<Self, CommandError> {
+        let mut args = args.into_iter();
+        let (Some(key), Some(value)) = (args.next(), args.next()) else {
+            return Err(CommandError::WrongArity(Self::NAME));
+        };
+        let mut set = Set {
+            key,
+            value,
+            condition: None,
+            get: false,
+            expiry: None,
+        };
+        let mut expiry_option = None;
+        while let Some(option) = args.next() {
+            if let Some(expiry) = Expiry::parse(Self::NAME, &option, &mut args)? {
+                check_expiry_option(&mut expiry_option, &option)?;
+                set.expiry = Some(expiry);
+                continue;
+            }
+            let condition = match option.to_ascii_uppercase().as_slice() {
+                b"NX" => SetCondition::Nx,
+                b"XX" => SetCondition::Xx,
+                b"GET" => {
+                    set.get = true;
+                    continue;
+                }
+                b"KEEPTTL" => {
+                    check_expiry_option(&mut expiry_option, &option)?;
+                    set.expiry = Some(Expiry::KeepTtl);
+                    continue;
+                }
+                _ => return Err(CommandError::SyntaxError),
+            };
+            // NX and XX conflict, but either may be repeated
+            if set
+                .condition
+                .replace(condition)
+                .is_some_and(|current| current != condition)
+            {
+                return Err(CommandError::SyntaxError);
+            }
+        }
+        Ok(set)
+    }
+}
+
+impl CommandSpec for GetSet {
+    const NAME: &'static str = "getset";
+    const ARITY: isize = 3;
+
+    fn parse(args: Vec<Bytes>) -> Result<Self, CommandError> {
+        let [key, value] = fixed_args(Self::NAME, args)?;
+        Ok(GetSet { key, value })
+    }
+}
+
+impl CommandSpec for GetDel {
+    const NAME: &'static str = "getdel";
+    const ARITY: isize = 2;
+
+    fn parse(args: Vec<Bytes>) -> Result<Self, CommandError> {
+        let [key] = fixed_args(Self::NAME, args)?;
+        Ok(GetDel { key })
+    }
+}
+
+impl CommandSpec for GetEx {
+    const NAME: &'static str = "getex";
+    const ARITY: isize = -2;
+
+    fn parse(args: Vec<Bytes>) -> Result<Self, CommandError> {
+        let mut args = args.into_iter();
+        let Some(key) = args.next() else {
+            return Err(CommandError::WrongArity(Self::NAME));
+        };
+        let (mut expiry, mut expiry_option) = (None, None);
+        while let Some(option) = args.next() {
+            let parsed = match Expiry::parse(Self::NAME, &option, &mut args)? {
+                Some(parsed) => parsed,
+                None if option.eq_ignore_ascii_case(b"PERSIST") => Expiry::Persist,
+                None => return Err(CommandError::SyntaxError),
+            };
+            check_expiry_option(&mut expiry_option, &option)?;
+            expiry = Some(parsed);
+        }
+        Ok(GetEx { key, expiry })
+    }
+}
+
+impl CommandSpec for MGet {
+    const NAME: &'static str = "mget";
+    const ARITY: isize = -2;
+
+    fn parse(args: Vec<Bytes>) -> Result<Self, CommandError> {
+        Ok(MGet { keys: args })
+    }
+}
+
+impl CommandSpec for MSet {
+    const NAME: &'static str = "mset";
+    const ARITY: isize = -3;
+
+    fn parse(args: Vec<Bytes>) -> Result<Self, CommandError> {
+        Ok(MSet {
+            pairs: into_pairs(Self::NAME, args)?,
+        })
+    }
+}
+
+impl CommandSpec for MSetNx {
+    const NAME: &'static str = "msetnx";
+    const ARITY: isize = -3;
+
+    fn parse(args: Vec<Bytes>) -> Result<Self, CommandError> {
+        Ok(MSetNx {
+            pairs: into_pairs(Self::NAME, args)?,
+        })
+    }
+}
+
+impl CommandSpec for Append {
+    const NAME: &'static str = "append";
+    const ARITY: isize = 3;
+
+    fn parse(args: Vec<Bytes>) -> Result<Self, CommandError> {
+        let [key, value] = fixed_args(Self::NAME, args)?;
+        Ok(Append { key, value })
+    }
+}
+
+impl CommandSpec for StrLen {
+    const NAME: &'static str = "strlen";
+    const ARITY: isize = 2;
+
+    fn parse(args: Vec<Bytes>) -> Result<Self, CommandError> {
+        let [key] = fixed_args(Self::NAME, args)?;
+        Ok(StrLen { key })
+    }
+}
+
+impl CommandSpec for SetRange {
+    const NAME: &'static str = "setrange";
+    const ARITY: isize = 4;
+
+    fn parse(args: Vec<Bytes>) -> Result<Self, CommandError> {
+        let [key, offset, value] = fixed_args(Self::NAME, args)?;
+        let offset =
+            usize::try_from(parse_integer(&offset)?).map_err(|_| CommandError::OffsetOutOfRange)?;
+        Ok(SetRange { key, offset, value })
+    }
+}
+
+impl CommandSpec for GetRange {
+    const NAME: &'static str = "getrange";
+    const ARITY: isize = 4;
+
+    fn parse(args: Vec<Bytes>) -> Result<Self, CommandError> {
+        let [key, start, end] = fixed_args(Self::NAME, args)?;
+        Ok(GetRange {
+            key,
+            start: parse_integer(&start)?,
+            end: parse_integer(&end)?,
+        })
+    }
+}
+
+impl CommandSpec for Lcs {
+    const NAME: &'static str = "lcs";
+    const ARITY: isize = -3;
+
+    fn parse(args: Vec<Bytes>) -> Result<Self, CommandError> {
+        let mut args = args.into_iter();
+        let (Some(key1), Some(key2)) = (args.next(), args.next()) else {
+            return Err(CommandError::WrongArity(Self::NAME));
+        };
+        let mut lcs = Lcs {
+            key1,
+            key2,
+            len: false,
+            idx: false,
+            min_match_len: 0,
+            with_match_len: false,
+        };
+        while let Some(option) = args.next() {
+            match option.to_ascii_uppercase().as_slice() {
+                b"LEN" => lcs.len = true,
+                b"IDX" => lcs.idx = true,
+                b"WITHMATCHLEN" => lcs.with_match_len = true,
+                b"MINMATCHLEN" => {
+                    let len = parse_integer(&args.next().ok_or(CommandError::SyntaxError)?)?;
+                    lcs.min_match_len = len.max(0) as usize;
+                }
+                _ => return Err(CommandError::SyntaxError),
+            }
+        }
+        if lcs.len && lcs.idx {
+            return Err(CommandError::InvalidArgument(
+                "If you want both the length and indexes, please just use IDX.",
+            ));
+        }
+        Ok(lcs)
+    }
+}
+
+impl CommandExecutor for Get {
+    fn execute(self, backend: &Backend) -> Result<RespFrame, CommandError> {
+        let value = backend.read(|keyspace, now| {
+            keyspace
+                .get(&self.key, now)
+                .map(|entry| entry.value.clone())
+        });
+        Ok(bulk_or_nil(value))
+    }
+}
+
+impl CommandExecutor for Set {
+    fn execute(self, backend: &Backend) -> Result<RespFrame, CommandError> {
+        backend.write(|keyspace, now| {
+            let current = keyspace.get(&self.key, now);
+            let should_set = match self.condition {
+                Some(SetCondition::Nx) => current.is_none(),
+                Some(SetCondition::Xx) => current.is_some(),
+                None => true,
+            };
+            let old_value = current.map(|entry| entry.value.clone());
+
+            if should_set {
+                let current_expiry = current.and_then(|entry| entry.expires_at);
+                let expires_at = match self.expiry {
+                    Some(expiry) => expiry.resolve(Set::NAME, current_expiry, now)?,
+                    None => None,
+                };
+                let value = stored(&self.value);
+                keyspace.insert(self.key, Entry::with_expiry(value, expires_at));
+            }
+
+            Ok(match (self.get, should_set) {
+                (true, _) => bulk_or_nil(old_value),
+                (false, true) => ok(),
+                (false, false) => BulkNullString.into(),
+            })
+        })
+    }
+}
+
+impl CommandExecutor for GetSet {
+    fn execute(self, backend: &Backend) -> Result<RespFrame, CommandError> {
+        let old = backend.write(|keyspace, now| {
+            let old = keyspace.remove(&self.key, now);
+            keyspace.insert(self.key, Entry::new(stored(&self.value)));
+            old
+        });
+        Ok(bulk_or_nil(old.map(|entry| entry.value)))
+    }
+}
+
+impl CommandExecutor for GetDel {
+    fn execute(self, backend: &Backend) -> Result<RespFrame, CommandError> {
+        let old = backend.write(|keyspace, now| keyspace.remove(&self.key, now));
+        Ok(bulk_or_nil(old.map(|entry| entry.value)))
+    }
+}
+
+impl CommandExecutor for GetEx {
+    fn execute(self, backend: &Backend) -> Result<RespFrame, CommandError> {
+        backend.write(|keyspace, now| {
+            let Some(entry) = keyspace.get(&self.key, now) else {
+                return Ok(BulkNullString.into());
+            };
+            let value = entry.value.clone();
+            if let Some(expiry) = self.expiry {
+                let expires_at = expiry.resolve(GetEx::NAME, entry.expires_at, now)?;
+                keyspace.set_expiry(&self.key, expires_at, now);
+            }
+            Ok(BulkString::from(value).into())
+        })
+    }
+}
+
+impl CommandExecutor for MGet {
+    fn execute(self, backend: &Backend) -> Result<RespFrame, CommandError> {
+        let values = backend.read(|keyspace, now| {
+            self.keys
+                .iter()
+                .map(|key| bulk_or_nil(keyspace.get(key, now).map(|entry| entry.value.clone())))
+                .collect::<Vec<_>>()
+        });
+        Ok(RespFrameArray::new(values).into())
+    }
+}
+
+impl CommandExecutor for MSet {
+    fn execute(self, backend: &Backend) -> Result<RespFrame, CommandError> {
+        backend.write(|keyspace, _| {
+            for (key, value) in self.pairs {
+                keyspace.insert(key, Entry::new(stored(&value)));
+            }
+        });
+        Ok(ok())
+    }
+}
+
+impl CommandExecutor for MSetNx {
+    fn execute(self, backend: &Backend) -> Result<RespFrame, CommandError> {
+        let set = backend.write(|keyspace, now| {
+            if self
+                .pairs
+                .iter()
+                .any(|(key, _)| keyspace.contains(key, now))
+            {
+                return false;
+            }
+            for (key, value) in self.pairs {
+                keyspace.insert(key, Entry::new(stored(&value)));
+            }
+            true
+        });
+        Ok(RespFrame::from(set as i64))
+    }
+}
+
+impl CommandExecutor for Append {
+    fn execute(self, backend: &Backend) -> Result<RespFrame, CommandError> {
+        backend.write(|keyspace, now| {
+            let Some(entry) = keyspace.get_mut(&self.key, now) else {
+                let len = self.value.len();
+                keyspace.insert(self.key, Entry::new(stored(&self.value)));
+                return Ok(RespFrame::from(len as i64));
+            };
+            let len = entry.value.len() + self.value.len();
+            check_string_len(len)?;
+            let mut value = Vec::with_capacity(len);
+            value.extend_from_slice(&entry.value);
+            value.extend_from_slice(&self.value);
+            entry.value = value.into();
+            Ok(RespFrame::from(len as i64))
+        })
+    }
+}
+
+impl CommandExecutor for StrLen {
+    fn execute(self, backend: &Backend) -> Result<RespFrame, CommandError> {
+        let len = backend.read(|keyspace, now| {
+            keyspace
+                .get(&self.key, now)
+                .map_or(0, |entry| entry.value.len())
+        });
+        Ok(RespFrame::from(len as i64))
+    }
+}
+
+impl CommandExecutor for SetRange {
+    fn execute(self, backend: &Backend) -> Result<RespFrame, CommandError> {
+        backend.write(|keyspace, now| {
+            let entry = keyspace.get_mut(&self.key, now);
+            let current_len = entry.as_ref().map_or(0, |entry| entry.value.len());
+            // an empty value changes nothing, not even creating the key
+            if self.value.is_empty() {
+                return Ok(RespFrame::from(current_len as i64));
+            }
+            let end = self
+                .offset
+                .checked_add(self.value.len())
+                .ok_or(CommandError::StringTooLong)?;
+            check_string_len(end)?;
+
+            let mut value = entry
+                .as_ref()
+                .map_or(Vec::new(), |entry| entry.value.to_vec());
+            if value.len() < end {
+                value.resize(end, 0);
+            }
+            value[self.offset..end].copy_from_slice(&self.value);
+            let len = value.len();
+            match entry {
+                Some(entry) => entry.value = value.into(),
+                None => keyspace.insert(self.key, Entry::new(value)),
+            }
+            Ok(RespFrame::from(len as i64))
+        })
+    }
+}
+
+impl CommandExecutor for GetRange {
+    fn execute(self, backend: &Backend) -> Result<RespFrame, CommandError> {
+        let value = backend.read(|keyspace, now| {
+            keyspace
+                .get(&self.key, now)
+                .map(|entry| entry.value.clone())
+        });
+        let value = value.unwrap_or_default();
+        Ok(BulkString::from(substring(value, self.start, self.end)).into())
+    }
+}
+
+// the bytes from `start` to `end` inclusive, negative offsets count from the end
+fn substring(value: Bytes, start: i64, end: i64) -> Bytes {
+    let len = value.len() as i64;
+    if start < 0 && end < 0 && start > end {
+        return Bytes::new();
+    }
+    let start = if start < 0 { len + start } else { start }.max(0);
+    let end = if end < 0 { len + end } else { end }.max(0).min(len - 1);
+    if len == 0 || start > end {
+        return Bytes::new();
+    }
+    value.slice(start as usize..=end as usize)
+}
+
+impl CommandExecutor for Lcs {
+    fn execute(self, backend: &Backend) -> Result<RespFrame, CommandError> {
+        // missing keys are compared as empty strings
+        let (a, b) = backend.read(|keyspace, now| {
+            let value = |key: &[u8]| {
+                keyspace
+                    .get(key, now)
+                    .map(|entry| entry.value.clone())
+                    .unwrap_or_default()
+            };
+            (value(&self.key1), value(&self.key2))
+        });
+
+        let table_len = (a.len() + 1)
+            .checked_mul(b.len() + 1)
+            .and_then(|cells| cells.checked_mul(std::mem::size_of::<u32>()));
+        if table_len.is_none_or(|len| len > MAX_STRING_LEN) {
+            return Err(CommandError::InvalidArgument(
+                "Insufficient memory, transient memory for LCS exceeds proto-max-bulk-len",
+            ));
+        }
+
+        let table = LcsTable::new(&a, &b);
+        if self.len {
+            return Ok(RespFrame::from(table.len() as i64));
+        }
+        if !self.idx {
+            return Ok(BulkString::new(table.common(&a, &b)).into());
+        }
+
+        let matches = table
+            .matches(&a, &b)
+            .into_iter()
+            .filter(|m| m.len >= self.min_match_len)
+            .map(|m| {
+                let mut frames: Vec<RespFrame> = vec![
+                    RespFrameArray::new(vec![(m.a.0 as i64).into(), (m.a.1 as i64).into()]).into(),
+                    RespFrameArray::new(vec![(m.b.0 as i64).into(), (m.b.1 as i64).into()]).into(),
+                ];
+                if self.with_match_len {
+                    frames.push((m.len as i64).into());
+                }
+                RespFrameArray::new(frames).into()
+            })
+            .collect::<Vec<RespFrame>>();
+        Ok(RespMap::new(vec![
+            (b"matches".into(), RespFrameArray::new(matches).into()),
+            (b"len".into(), (table.len() as i64).into()),
+        ])
+        .into())
+    }
+}
+
+// the dynamic programming table of the longest common subsequence, `cells[i][j]` is the
+// length of the LCS of `a[..i]` and `b[..j]`
+struct LcsTable {
+    width: usize,
+    cells: Vec<u32>,
+}
+
+#[derive(Debug, PartialEq)]
+struct LcsMatch {
+    a: (usize, usize),
+    b: (usize, usize),
+    len: usize,
+}
+
+impl LcsTable {
+    fn new(a: &[u8], b: &[u8]) -> Self {
+        let width = b.len() + 1;
+        let mut cells = vec![0u32; (a.len() + 1) * width];
+        for i in 1..=a.len() {
+            for j in 1..=b.len() {
+                cells[i * width + j] = if a[i - 1] == b[j - 1] {
+                    cells[(i - 1) * width + j - 1] + 1
+                } else {
+                    cells[(i - 1) * width + j].max(cells[i * width + j - 1])
+                };
+            }
+        }
+        LcsTable { width, cells }
+    }
+
+    fn get(&self, i: usize, j: usize) -> u32 {
+        self.cells[i * self.width + j]
+    }
+
+    fn len(&self) -> usize {
+        self.cells.last().copied().unwrap_or_default() as usize
+    }
+
+    // walks back from the end of both strings, like redis does
+    fn walk(&self, a: &[u8], b: &[u8], mut on_match: impl FnMut(usize, usize)) {
+        let (mut i, mut j) = (a.len(), b.len());
+        while i > 0 && j > 0 {
+            if a[i - 1] == b[j - 1] {
+                on_match(i - 1, j - 1);
+                i -= 1;
+                j -= 1;
+            } else if self.get(i - 1, j) > self.get(i, j - 1) {
+                i -= 1;
+            } else {
+                j -= 1;
+            }
+        }
+    }
+
+    fn common(&self, a: &[u8], b: &[u8]) -> Vec<u8> {
+        let mut common = Vec::with_capacity(self.len());
+        self.walk(a, b, |i, _| common.push(a[i]));
+        common.reverse();
+        common
+    }
+
+    // the contiguous ranges of the subsequence in both strings, last range first
+    fn matches(&self, a: &[u8], b: &[u8]) -> Vec<LcsMatch> {
+        let mut matches: Vec<LcsMatch> = Vec::new();
+        self.walk(a, b, |i, j| match matches.last_mut() {
+            Some(m) if m.a.0 == i + 1 && m.b.0 == j + 1 => {
+                m.a.0 = i;
+                m.b.0 = j;
+                m.len += 1;
+            }
+            _ => matches.push(LcsMatch {
+                a: (i, i),
+                b: (j, j),
+                len: 1,
+            }),
+        });
+        matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        backend::ManualClock,
+        cmd::{
+            tests::{request, run},
+            Command,
+        },
+        RespDecode, RespEncode,
+    };
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    fn nil() -> RespFrame {
+        BulkNullString.into()
+    }
+
+    #[test]
+    fn test_get_set() -> Result<()> {
+        let backend = Backend::new();
+        assert_eq!(run(&backend, &["GET", "k"])?, nil());
+        assert_eq!(run(&backend, &["SET", "k", "v"])?, ok());
+        assert_eq!(run(&backend, &["get", "k"])?, b"v".into());
+
+        assert_eq!(run(&backend, &["SET", "k", "v2", "NX"])?, nil());
+        assert_eq!(
+            run(&backend, &["SET", "k", "v2", "XX", "GET"])?,
+            b"v".into()
+        );
+        assert_eq!(run(&backend, &["SET", "other", "v", "XX"])?, nil());
+        assert_eq!(run(&backend, &["SET", "other", "v", "NX", "GET"])?, nil());
+        assert_eq!(run(&backend, &["GET", "other"])?, b"v".into());
+        assert_eq!(run(&backend, &["GET", "k"])?, b"v2".into());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_stored_values_are_copied() -> Result<()> {
+        let backend = Backend::new();
+        let requests: [&[&str]; 6] = [
+            &["SET", "k1", "v1"],
+            &["GETSET", "k2", "v2"],
+            &["MSET", "k3", "v3", "k4", "v4"],
+            &["MSETNX", "k5", "v5"],
+            &["APPEND", "k6", "v6"],
+            &["SETRANGE", "k7", "0", "v7"],
+        ];
+        let mut buffers = Vec::new();
+        for args in requests {
+            let mut buf = BytesMut::from(&request(args).encode()[..]);
+            buffers.push(buf.as_ptr_range());
+            Command::try_from(RespFrame::decode(&mut buf)?)?.execute(&backend)?;
+        }
+
+        for i in 1..=7 {
+            let key = format!("k{}", i);
+            let value = backend.read(|keyspace, now| keyspace.get(key.as_bytes(), now).cloned());
+            let value = value.unwrap().value;
+            assert_eq!(value, format!("v{}", i));
+            assert!(buffers.iter().all(|buf| !buf.contains(&value.as_ptr())));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_set_expiry() -> Result<()> {
+        let backend = Backend::new();
+        run(&backend, &["SET", "k", "v", "EX", "100"])?;
+        let expires_at = backend.read(|keyspace, _| keyspace.get(b"k", 0).unwrap().expires_at);
+        assert!(expires_at.is_some());
+
+        run(&backend, &["SET", "k", "v2", "KEEPTTL"])?;
+        let entry = backend.read(|keyspace, _| keyspace.get(b"k", 0).cloned().unwrap());
+        assert_eq!(entry, Entry::with_expiry("v2", expires_at));
+
+        run(&backend, &["SET", "k", "v3"])?;
+        let entry = backend.read(|keyspace, _| keyspace.get(b"k", 0).cloned().unwrap());
+        assert_eq!(entry, Entry::new("v3"));
+
+        // already expired
+        run(&backend, &["SET", "k", "v", "PXAT", "1"])?;
+        assert_eq!(run(&backend, &["GET", "k"])?, nil());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_set_errors() {
+        let backend = Backend::new();
+        let cases: [(&[&str], CommandError); 7] = [
+            (&["SET", "k"], CommandError::WrongArity("set")),
+            (&["SET", "k", "v", "NX", "XX"], CommandError::SyntaxError),
+            (&["SET", "k", "v", "EX"], CommandError::SyntaxError),
+            (
+                &["SET", "k", "v", "EX", "1", "PX", "1"],
+                CommandError::SyntaxError,
+            ),
+            (
+                &["SET", "k", "v", "KEEPTTL", "EX", "1"],
+                CommandError::SyntaxError,
+            ),
+            (&["SET", "k", "v", "EX", "abc"], CommandError::NotInteger),
+            (
+                &["SET", "k", "v", "EX", "0"],
+                CommandError::InvalidExpireTime("set"),
+            ),
+        ];
+        for (args, expected) in cases {
+            assert_eq!(run(&backend, args).unwrap_err(), expected);
+        }
+        assert_eq!(
+            run(&backend, &["SET", "k", "v", "EX", "9223372036854775807"]).unwrap_err(),
+            CommandError::InvalidExpireTime("set")
+        );
+    }
+
+    #[test]
+    fn test_set_repeated_options() -> Result<()> {
+        let backend = Backend::with_clock(ManualClock::new(1000));
+        let expires_at = |backend: &Backend| {
+            backend.read(|keyspace, now| keyspace.get(b"k", now).and_then(|entry| entry.expires_at))
+        };
+
+        assert_eq!(run(&backend, &["SET", "k", "v", "NX", "nx"])?, ok());
+        assert_eq!(
+            run(&backend, &["SET", "k", "v2", "XX", "XX", "GET", "GET"])?,
+            b"v".into()
+        );
+        assert_eq!(
+            run(&backend, &["SET", "k", "v", "EX", "10", "ex", "20"])?,
+            ok()
+        );
+        assert_eq!(expires_at(&backend), Some(21_000));
+        assert_eq!(
+            run(&backend, &["SET", "k", "v", "KEEPTTL", "KEEPTTL"])?,
+            ok()
+        );
+        assert_eq!(expires_at(&backend), Some(21_000));
+
+        run(&backend, &["GETEX", "k", "PX", "1", "PX", "500"])?;
+        assert_eq!(expires_at(&backend), Some(1500));
+        run(&backend, &["GETEX", "k", "PERSIST", "PERSIST"])?;
+        assert_eq!(expires_at(&backend), None);
+
+        // only different options of the same kind conflict
+        for args in [
+            &["SET", "k", "v", "NX", "XX", "NX"][..],
+            &["SET", "k", "v", "EX", "1", "EX", "1", "PX", "1"],
+            &["SET", "k", "v", "KEEPTTL", "PXAT", "1"],
+            &["GETEX", "k", "EXAT", "1", "PERSIST"],
+        ] {
+            assert_eq!(run(&backend, args), Err(CommandError::SyntaxError));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_getset_getdel_getex() -> Result<()> {
+        let backend = Backend::new();
+        assert_eq!(run(&backend, &["GETSET", "k", "1"])?, nil());
+        assert_eq!(run(&backend, &["GETSET", "k", "2"])?, b"1".into());
+        assert_eq!(run(&backend, &["GETDEL", "k"])?, b"2".into());
+        assert_eq!(run(&backend, &["GETDEL", "k"])?, nil());
+
+        assert_eq!(run(&backend, &["GETEX", "k", "EX", "10"])?, nil());
+        run(&backend, &["SET", "k", "v"])?;
+        assert_eq!(run(&backend, &["GETEX", "k", "PX", "10000"])?, b"v".into());
+        let expires_at = backend.read(|keyspace, _| keyspace.get(b"k", 0).unwrap().expires_at);
+        assert!(expires_at.is_some());
+        assert_eq!(run(&backend, &["GETEX", "k", "PERSIST"])?, b"v".into());
+        let expires_at = backend.read(|keyspace, _| keyspace.get(b"k", 0).unwrap().expires_at);
+        assert_eq!(expires_at, None);
+        assert_eq!(
+            run(&backend, &["GETEX", "k", "PERSIST", "EX", "1"]).unwrap_err(),
+            CommandError::SyntaxError
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_mget_mset_msetnx() -> Result<()> {
+        let backend = Backend::new();
+        assert_eq!(run(&backend, &["MSET", "a", "1", "b", "2"])?, ok());
+        assert_eq!(
+            run(&backend, &["MGET", "a", "missing", "b"])?,
+            RespFrameArray::new(vec![b"1".into(), nil(), b"2".into()]).into()
+        );
+        assert_eq!(
+            run(&backend, &["MSET", "a", "1", "b"]).unwrap_err(),
+            CommandError::WrongArity("mset")
+        );
+
+        assert_eq!(run(&backend, &["MSETNX", "b", "3", "c", "3"])?, 0.into());
+        assert_eq!(run(&backend, &["GET", "c"])?, nil());
+        assert_eq!(run(&backend, &["MSETNX", "c", "3", "d", "4"])?, 1.into());
+        assert_eq!(run(&backend, &["GET", "d"])?, b"4".into());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_append_strlen() -> Result<()> {
+        let backend = Backend::new();
+        assert_eq!(run(&backend, &["STRLEN", "k"])?, 0.into());
+        assert_eq!(run(&backend, &["APPEND", "k", "Hello"])?, 5.into());
+        assert_eq!(run(&backend, &["APPEND", "k", " World"])?, 11.into());
+        assert_eq!(run(&backend, &["GET", "k"])?, b"Hello World".into());
+        assert_eq!(run(&backend, &["STRLEN", "k"])?, 11.into());
+        Ok(())
+    }
+
+    #[test]
+    fn test_setrange_getrange() -> Result<()> {
+        let backend = Backend::new();
+        assert_eq!(run(&backend, &["SETRANGE", "k", "5", ""])?, 0.into());
+        assert_eq!(run(&backend, &["GET", "k"])?, nil());
+        assert_eq!(run(&backend, &["SETRANGE", "k", "2", "ab"])?, 4.into());
+        assert_eq!(run(&backend, &["GET", "k"])?, b"\0\0ab".into());
+
+        run(&backend, &["SET", "k", "Hello World"])?;
+        assert_eq!(run(&backend, &["SETRANGE", "k", "6", "Redis"])?, 11.into());
+        assert_eq!(run(&backend, &["GET", "k"])?, b"Hello Redis".into());
+        assert_eq!(
+            run(&backend, &["SETRANGE", "k", "-1", "x"]).unwrap_err(),
+            CommandError::OffsetOutOfRange
+        );
+        assert_eq!(
+            run(&backend, &["SETRANGE", "k", "536870912", "x"]).unwrap_err(),
+            CommandError::StringTooLong
+        );
+
+        run(&backend, &["SET", "k", "This is a string"])?;
+        let cases = [
+            ("0", "3", &b"This"[..]),
+            ("-3", "-1", b"ing"),
+            ("0", "-1", b"This is a string"),
+            ("10", "100", b"string"),
+            ("-1", "-5", b""),
+            ("5", "3", b""),
+            ("-100", "2", b"Thi"),
+        ];
+        for (start, end, expected) in cases {
+            assert_eq!(
+                run(&backend, &["GETRANGE", "k", start, end])?,
+                BulkString::new(expected).into()
+            );
+        }
+        assert_eq!(
+            run(&backend, &["GETRANGE", "missing", "0", "-1"])?,
+            b"".into()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_lcs() -> Result<()> {
+        let backend = Backend::new();
+        run(&backend, &["MSET", "key1", "ohmytext", "key2", "mynewtext"])?;
+        assert_eq!(run(&backend, &["LCS", "key1", "key2"])?, b"mytext".into());
+        assert_eq!(run(&backend, &["LCS", "key1", "key2", "LEN"])?, 6.into());
+
+        let range = |start: i64, end: i64| -> RespFrame {
+            RespFrameArray::new(vec![start.into(), end.into()]).into()
+        };
+        assert_eq!(
+            run(&backend, &["LCS", "key1", "key2", "IDX"])?,
+            RespMap::new(vec![
+                (
+                    b"matches".into(),
+                    RespFrameArray::new(vec![
+                        RespFrameArray::new(vec![range(4, 7), range(5, 8)]).into(),
+                        RespFrameArray::new(vec![range(2, 3), range(0, 1)]).into(),
+                    ])
+                    .into()
+                ),
+                (b"len".into(), 6.into()),
+            ])
+            .into()
+        );
+        assert_eq!(
+            run(
+                &backend,
+                &[
+                    "LCS",
+                    "key1",
+                    "key2",
+                    "IDX",
+                    "MINMATCHLEN",
+                    "4",
+                    "WITHMATCHLEN"
+                ]
+            )?,
+            RespMap::new(vec![
+                (
+                    b"matches".into(),
+                    RespFrameArray::new(vec![RespFrameArray::new(vec![
+                        range(4, 7),
+                        range(5, 8),
+                        4.into()
+                    ])
+                    .into()])
+                    .into()
+                ),
+                (b"len".into(), 6.into()),
+            ])
+            .into()
+        );
+        assert_eq!(run(&backend, &["LCS", "key1", "missing"])?, b"".into());
+        assert!(matches!(
+            run(&backend, &["LCS", "key1", "key2", "LEN", "IDX"]),
+            Err(CommandError::InvalidArgument(_))
+        ));
+
+        Ok(())
+    }
+}
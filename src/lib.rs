@@ -1,3 +1,17 @@
+pub mod backend;
+pub mod cmd;
+pub mod network;
 mod resp;
 
-pub use resp::RespFrame;
+pub use resp::{
+    BigNumber, BulkError, BulkNullString, BulkString, FromRespFrame, IntoRespFrame, RawSimpleError,
+    RawSimpleString, RespAttribute, RespCodec, RespCodecError, RespConvertError, RespDecode,
+    RespEncode, RespError, RespFrame, RespFrameArray, RespLimits, RespMap, RespNullArray,
+    RespParser, RespPush, RespSet, RespVersion, SimpleError, SimpleNull, SimpleString,
+    VerbatimString,
+};
+
+pub use backend::Backend;
+
+#[cfg(feature = "serde")]
+pub use resp::{from_frame, to_frame, RespSerdeError};
@@ -1,3 +1,21 @@
-fn main() {
-    println!("Hello, world!");
+use anyhow::Result;
+use rust_redis_server::{network, Backend};
+use tokio::net::TcpListener;
+use tracing::info;
+
+const DEFAULT_PORT: u16 = 6379;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    tracing_subscriber::fmt::init();
+
+    let port = match std::env::args().skip_while(|arg| arg != "--port").nth(1) {
+        Some(port) => port.parse()?,
+        None => DEFAULT_PORT,
+    };
+    let addr = format!("0.0.0.0:{}", port);
+    let listener = TcpListener::bind(&addr).await?;
+    info!("Ready to accept connections on {}", addr);
+
+    network::serve(listener, Backend::new()).await
 }
@@ -0,0 +1,163 @@
+use bytes::BytesMut;
+use tokio::{
+    io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
+    net::TcpListener,
+};
+use tracing::{info, warn};
+
+use crate::{
+    cmd::{error_reply, Command, CommandExecutor},
+    Backend, RespError, RespFrame, RespParser, RespVersion,
+};
+
+// most requests fit in one read, larger ones grow the buffer as needed
+const READ_BUF_CAP: usize = 16 * 1024;
+
+/// Accepts connections on `listener` forever, serving each one on its own task, while expired
+/// keys are reclaimed in the background.
+pub async fn serve(listener: TcpListener, backend: Backend) -> anyhow::Result<()> {
+    tokio::spawn(backend.clone().active_expire());
+    loop {
+        let (stream, addr) = listener.accept().await?;
+        info!("Accepted connection from {}", addr);
+        let backend = backend.clone();
+        tokio::spawn(async move {
+            match handle_connection(stream, backend).await {
+                Ok(()) => info!("Connection from {} closed", addr),
+                Err(e) => warn!("Connection from {} failed: {}", addr, e),
+            }
+        });
+    }
+}
+
+/// Serves requests from `stream` until the peer closes it.
+///
+/// Every complete request in the read buffer is answered before reading again, so the
+/// replies to a pipelined batch go out in a single write. A request that has only partially
+/// arrived stays in the buffer until the rest is read.
+pub async fn handle_connection<S>(mut stream: S, backend: Backend) -> anyhow::Result<()>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let mut buf = BytesMut::with_capacity(READ_BUF_CAP);
+    let mut out = BytesMut::new();
+    let mut parser = RespParser::new();
+    // clients speak RESP2 until they send `HELLO 3`
+    let version = RespVersion::Resp2;
+
+    loop {
+        loop {
+            match parser.parse_request(&mut buf) {
+                // like redis, an empty request is ignored rather than answered
+                Ok(RespFrame::Array(request)) if request.is_empty() => {}
+                Ok(request) => {
+                    let reply = dispatch(request, &backend);
+                    reply.encode_to_with_version(&mut out, version);
+                }
+                Err(RespError::NotComplete) => break,
+                Err(e) => {
+                    // like redis, report the protocol error and drop the connection
+                    let reply = error_reply(format!("ERR Protocol error: {}", e));
+                    reply.encode_to_with_version(&mut out, version);
+                    stream.write_all(&out).await?;
+                    return Err(e.into());
+                }
+            }
+        }
+
+        if !out.is_empty() {
+            stream.write_all(&out).await?;
+            out.clear();
+        }
+
+        if stream.read_buf(&mut buf).await? == 0 {
+            return Ok(());
+        }
+    }
+}
+
+fn dispatch(request: RespFrame, backend: &Backend) -> RespFrame {
+    Command::try_from(request)
+        .and_then(|command| command.execute(backend))
+        .unwrap_or_else(Into::into)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use tokio::io::{duplex, AsyncReadExt, AsyncWriteExt};
+
+    async fn read_reply(client: &mut tokio::io::DuplexStream, len: usize) -> Result<Vec<u8>> {
+        let mut reply = vec![0; len];
+        client.read_exact(&mut reply).await?;
+        Ok(reply)
+    }
+
+    #[tokio::test]
+    async fn test_handle_connection_replies() -> Result<()> {
+        let (mut client, server) = duplex(64);
+        let handle = tokio::spawn(handle_connection(server, Backend::new()));
+
+        client.write_all(b"*1\r\n$4\r\nPING\r\n").await?;
+        assert_eq!(read_reply(&mut client, 7).await?, b"+PONG\r\n");
+
+        // inline commands, as typed into telnet
+        client.write_all(b"ping hello\r\n").await?;
+        assert_eq!(read_reply(&mut client, 11).await?, b"$5\r\nhello\r\n");
+
+        client.write_all(b"*1\r\n$3\r\nFOO\r\n").await?;
+        let expected = b"-ERR unknown command 'FOO', with args beginning with: \r\n";
+        assert_eq!(read_reply(&mut client, expected.len()).await?, expected);
+
+        drop(client);
+        handle.await??;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_handle_connection_partial_and_pipelined() -> Result<()> {
+        let (mut client, server) = duplex(64);
+        let handle = tokio::spawn(handle_connection(server, Backend::new()));
+
+        client.write_all(b"*2\r\n$4\r\nPING\r\n$2\r").await?;
+        client.flush().await?;
+        tokio::task::yield_now().await;
+        client
+            .write_all(b"\nhi\r\n*1\r\n$4\r\nPING\r\n*1\r\n$4\r\nPING\r\n")
+            .await?;
+        assert_eq!(
+            read_reply(&mut client, 22).await?,
+            b"$2\r\nhi\r\n+PONG\r\n+PONG\r\n"
+        );
+
+        drop(client);
+        handle.await??;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_handle_connection_protocol_error() -> Result<()> {
+        let (mut client, server) = duplex(64);
+        let handle = tokio::spawn(handle_connection(server, Backend::new()));
+
+        client.write_all(b"*1\r\n$x\r\n").await?;
+        let mut reply = Vec::new();
+        client.read_to_end(&mut reply).await?;
+        assert!(reply.starts_with(b"-ERR Protocol error: "));
+        assert!(handle.await?.is_err());
+
+        // the reply names the bad byte without echoing the requests pipelined after it
+        let (mut client, server) = duplex(64);
+        let handle = tokio::spawn(handle_connection(server, Backend::new()));
+        client.write_all(b"*1\r\nX\r\n*3\r\n$3\r\nSET\r\n").await?;
+        let mut reply = Vec::new();
+        client.read_to_end(&mut reply).await?;
+        assert_eq!(
+            reply,
+            b"-ERR Protocol error: Invalid frame type: unknown frame type: 'X'\r\n"
+        );
+        assert!(handle.await?.is_err());
+        Ok(())
+    }
+}
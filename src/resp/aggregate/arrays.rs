@@ -1,11 +1,12 @@
 use std::{fmt::Debug, ops::Deref};
 
-use bytes::Buf;
+use bytes::{BufMut, BytesMut};
 
 use crate::{
     resp::{
-        calc_total_length, extract_fixed_data, parse_length, RespDecode, RespEncode, BUF_CAP,
-        CRLF_LEN,
+        extract_fixed_data, length_header_len,
+        parser::{decode_frame, frame_len},
+        put_length_header, RespDecode, RespEncode, RespError, RespLimits,
     },
     RespFrame,
 };
@@ -19,44 +20,41 @@ pub struct RespNullArray;
 impl RespDecode for RespFrameArray {
     const PREFIX: &'static str = "*";
 
-    fn decode(buf: &mut bytes::BytesMut) -> Result<Self, crate::resp::RespError> {
-        let (end, len) = parse_length(buf, Self::PREFIX)?;
-        let total_len = calc_total_length(buf, end, len, Self::PREFIX)?;
-
-        if buf.len() < total_len {
-            return Err(crate::resp::RespError::NotComplete);
-        }
-
-        buf.advance(end + CRLF_LEN);
-        let mut frames = Vec::with_capacity(len);
-        for _ in 0..len {
-            let frame = RespFrame::decode(buf)?;
-            frames.push(frame);
+    fn decode(buf: &mut BytesMut) -> Result<Self, RespError> {
+        match decode_frame(buf, Self::PREFIX, &RespLimits::default())? {
+            RespFrame::Array(frame) => Ok(frame),
+            frame => Err(RespError::InvalidFrame(format!(
+                "Expect RespFrameArray but got {:?}",
+                frame
+            ))),
         }
-
-        Ok(RespFrameArray::new(frames))
     }
 
-    fn expect_length(buf: &[u8]) -> Result<usize, crate::resp::RespError> {
-        let (end, len) = parse_length(buf, Self::PREFIX)?;
-        calc_total_length(buf, end, len, Self::PREFIX)
+    fn expect_length(buf: &[u8]) -> Result<usize, RespError> {
+        frame_len(buf, Self::PREFIX, &RespLimits::default())
     }
 }
 
 impl RespEncode for RespFrameArray {
-    fn encode(self) -> Vec<u8> {
-        let mut buf = Vec::with_capacity(BUF_CAP);
-        buf.extend_from_slice(&format!("*{}\r\n", self.0.len()).into_bytes());
-        for frame in self.0 {
-            buf.extend_from_slice(&frame.encode())
+    fn encode_to<B: BufMut>(&self, buf: &mut B) {
+        put_length_header(buf, b'*', self.0.len());
+        for frame in &self.0 {
+            frame.encode_to(buf);
         }
-        buf
+    }
+
+    fn encoded_len(&self) -> usize {
+        length_header_len(self.0.len()) + self.0.iter().map(|f| f.encoded_len()).sum::<usize>()
     }
 }
 
 impl RespEncode for RespNullArray {
-    fn encode(self) -> Vec<u8> {
-        b"*-1\r\n".to_vec()
+    fn encode_to<B: BufMut>(&self, buf: &mut B) {
+        buf.put_slice(b"*-1\r\n");
+    }
+
+    fn encoded_len(&self) -> usize {
+        5
     }
 }
 
@@ -113,6 +111,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_array_encode_to_preallocated_buffer() {
+        let frame: RespFrame = RespFrameArray::new(
+            (0..1000)
+                .map(|i| {
+                    RespFrameArray::new(vec![
+                        BulkString::new(format!("key:{}", i)).into(),
+                        (i as i64 - 500).into(),
+                        SimpleString::new("OK").into(),
+                    ])
+                    .into()
+                })
+                .collect::<Vec<RespFrame>>(),
+        )
+        .into();
+
+        let len = frame.encoded_len();
+        let mut buf = BytesMut::with_capacity(len);
+        let ptr = buf.as_ptr();
+        frame.encode_to(&mut buf);
+
+        assert_eq!(buf.len(), len);
+        assert_eq!(buf.as_ptr(), ptr);
+        assert!(buf.starts_with(b"*1000\r\n*3\r\n$5\r\nkey:0\r\n:-500\r\n+OK\r\n"));
+        assert_eq!(&buf[..], &frame.encode()[..]);
+    }
+
     #[test]
     fn test_null_array_encode() {
         let frame: RespFrame = RespNullArray.into();
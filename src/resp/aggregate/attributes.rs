@@ -0,0 +1,130 @@
+use bytes::{BufMut, BytesMut};
+
+use crate::{
+    resp::{
+        parser::{decode_frame, frame_len},
+        put_length_header, RespDecode, RespEncode, RespError, RespLimits, RespMap,
+    },
+    RespFrame,
+};
+
+/// A RESP3 attribute map together with the reply frame it annotates. On the wire the
+/// attribute is sent as `|` followed by map pairs, immediately before the reply.
+#[derive(Debug, PartialEq, PartialOrd)]
+pub struct RespAttribute {
+    pub attributes: RespMap,
+    pub frame: Box<RespFrame>,
+}
+
+impl RespAttribute {
+    pub fn new(attributes: impl Into<RespMap>, frame: impl Into<RespFrame>) -> Self {
+        RespAttribute {
+            attributes: attributes.into(),
+            frame: Box::new(frame.into()),
+        }
+    }
+
+    /// Drops the attributes and returns the annotated reply.
+    pub fn into_frame(self) -> RespFrame {
+        *self.frame
+    }
+}
+
+impl RespDecode for RespAttribute {
+    const PREFIX: &'static str = "|";
+
+    fn decode(buf: &mut BytesMut) -> Result<Self, RespError> {
+        match decode_frame(buf, Self::PREFIX, &RespLimits::default())? {
+            RespFrame::Attribute(frame) => Ok(frame),
+            frame => Err(RespError::InvalidFrame(format!(
+                "Expect RespAttribute but got {:?}",
+                frame
+            ))),
+        }
+    }
+
+    fn expect_length(buf: &[u8]) -> Result<usize, RespError> {
+        frame_len(buf, Self::PREFIX, &RespLimits::default())
+    }
+}
+
+impl RespEncode for RespAttribute {
+    fn encode_to<B: BufMut>(&self, buf: &mut B) {
+        put_length_header(buf, b'|', self.attributes.len());
+        for (key, value) in self.attributes.iter() {
+            key.encode_to(buf);
+            value.encode_to(buf);
+        }
+        self.frame.encode_to(buf);
+    }
+
+    fn encoded_len(&self) -> usize {
+        // the attributes are laid out exactly like a map, only the prefix differs
+        self.attributes.encoded_len() + self.frame.encoded_len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::resp::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    fn popularity() -> RespMap {
+        RespMap::new(vec![(
+            SimpleString::new("key-popularity").into(),
+            RespFrameArray::new(vec![b"a".into(), 0.1923.into()]).into(),
+        )])
+    }
+
+    #[test]
+    fn test_attribute_encode() {
+        let frame: RespFrame = RespAttribute::new(popularity(), 2039123).into();
+        assert_eq!(
+            &frame.encode(),
+            b"|1\r\n+key-popularity\r\n*2\r\n$1\r\na\r\n,+0.1923\r\n:+2039123\r\n"
+        );
+    }
+
+    #[test]
+    fn test_attribute_decode() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"|1\r\n+key-popularity\r\n*2\r\n$1\r\na\r\n,0.1923\r\n:2039123\r\n");
+
+        let frame = RespFrame::decode(&mut buf)?;
+        assert_eq!(frame, RespAttribute::new(popularity(), 2039123).into());
+        assert!(buf.is_empty());
+
+        buf.extend_from_slice(b"|1\r\n+key-popularity\r\n*2\r\n$1\r\na\r\n,0.1923\r\n");
+        let ret = RespFrame::decode(&mut buf);
+        assert_eq!(ret.unwrap_err(), RespError::NotComplete);
+
+        buf.extend_from_slice(b":2039123\r\n");
+        let frame = RespAttribute::decode(&mut buf)?;
+        assert_eq!(frame.into_frame(), 2039123.into());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_attribute_inside_array_decode() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*2\r\n|1\r\n+ttl\r\n:3600\r\n$5\r\nhello\r\n:1\r\n");
+
+        let frame = RespFrame::decode(&mut buf)?;
+        assert_eq!(
+            frame,
+            RespFrameArray::new(vec![
+                RespAttribute::new(
+                    vec![(SimpleString::new("ttl").into(), 3600.into())],
+                    BulkString::new("hello")
+                )
+                .into(),
+                1.into(),
+            ])
+            .into()
+        );
+
+        Ok(())
+    }
+}
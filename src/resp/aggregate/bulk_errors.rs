@@ -0,0 +1,102 @@
+use std::ops::Deref;
+
+use bytes::{Buf, BufMut, BytesMut};
+
+use crate::resp::{
+    check_bulk_end, length_header_len, parse_length, put_length_header, RespDecode, RespEncode,
+    RespError, CRLF, CRLF_LEN,
+};
+
+#[derive(Debug, PartialEq, PartialOrd)]
+pub struct BulkError(pub Vec<u8>);
+
+impl BulkError {
+    pub fn new(data: impl Into<Vec<u8>>) -> BulkError {
+        BulkError(data.into())
+    }
+}
+
+impl RespDecode for BulkError {
+    const PREFIX: &'static str = "!";
+    fn decode(buf: &mut BytesMut) -> Result<Self, RespError> {
+        let (end, len) = parse_length(buf, Self::PREFIX)?;
+        let remained = &buf[end + CRLF_LEN..];
+        if remained.len() < len + CRLF_LEN {
+            return Err(RespError::NotComplete);
+        }
+        check_bulk_end(remained, len)?;
+
+        buf.advance(end + CRLF_LEN);
+
+        let data = buf.split_to(len + CRLF_LEN);
+        Ok(BulkError::new(data[..len].to_vec()))
+    }
+
+    fn expect_length(buf: &[u8]) -> Result<usize, RespError> {
+        let (end, len) = parse_length(buf, Self::PREFIX)?;
+        Ok(end + CRLF_LEN + len + CRLF_LEN)
+    }
+}
+
+impl RespEncode for BulkError {
+    fn encode_to<B: BufMut>(&self, buf: &mut B) {
+        put_length_header(buf, b'!', self.len());
+        buf.put_slice(self);
+        buf.put_slice(CRLF);
+    }
+
+    fn encoded_len(&self) -> usize {
+        length_header_len(self.len()) + self.len() + CRLF_LEN
+    }
+}
+
+impl Deref for BulkError {
+    type Target = Vec<u8>;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RespFrame;
+
+    use super::*;
+    use anyhow::Result;
+
+    #[test]
+    fn test_bulk_error_encode() {
+        let frame: RespFrame = BulkError::new("SYNTAX invalid syntax").into();
+        assert_eq!(frame.encode(), b"!21\r\nSYNTAX invalid syntax\r\n");
+    }
+
+    #[test]
+    fn test_bulk_error_decode() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"!10\r\nERR a\r\nb c\r\n");
+
+        let frame = BulkError::decode(&mut buf)?;
+        assert_eq!(frame, BulkError::new("ERR a\r\nb c"));
+
+        buf.extend_from_slice(b"!5\r\nERROR");
+        let ret = BulkError::decode(&mut buf);
+        assert_eq!(ret.unwrap_err(), RespError::NotComplete);
+
+        buf.extend_from_slice(b"\r\n");
+        let frame = BulkError::decode(&mut buf)?;
+        assert_eq!(frame, BulkError::new("ERROR"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_bulk_error_decode_without_crlf() {
+        let mut buf = BytesMut::from(&b"!3\r\nabcXY:1\r\n"[..]);
+        let ret = BulkError::decode(&mut buf);
+        assert!(matches!(ret.unwrap_err(), RespError::InvalidFrame(_)));
+
+        let mut buf = BytesMut::from(&b"*2\r\n!3\r\nabcXY:1\r\n"[..]);
+        let ret = RespFrame::decode(&mut buf);
+        assert!(matches!(ret.unwrap_err(), RespError::InvalidFrame(_)));
+    }
+}
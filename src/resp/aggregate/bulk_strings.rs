@@ -1,11 +1,14 @@
 use std::ops::Deref;
 
-use bytes::{Buf, BytesMut};
+use bytes::{Buf, BufMut, Bytes, BytesMut};
 
-use crate::resp::{extract_fixed_data, parse_length, RespDecode, RespEncode, RespError, CRLF_LEN};
+use crate::resp::{
+    check_bulk_end, extract_fixed_data, length_header_len, parse_length, put_length_header,
+    RespDecode, RespEncode, RespError, CRLF, CRLF_LEN,
+};
 
 #[derive(Debug, PartialEq, PartialOrd)]
-pub struct BulkString(pub Vec<u8>);
+pub struct BulkString(pub Bytes);
 
 #[derive(Debug, PartialEq, PartialOrd)]
 pub struct BulkNullString;
@@ -23,14 +26,24 @@ impl RespDecode for BulkNullString {
 }
 
 impl RespEncode for BulkNullString {
-    fn encode(self) -> Vec<u8> {
-        b"$-1\r\n".to_vec()
+    fn encode_to<B: BufMut>(&self, buf: &mut B) {
+        buf.put_slice(b"$-1\r\n");
+    }
+
+    fn encoded_len(&self) -> usize {
+        5
     }
 }
 
 impl BulkString {
     pub fn new(data: impl Into<Vec<u8>>) -> BulkString {
-        BulkString(data.into())
+        BulkString(Bytes::from(data.into()))
+    }
+}
+
+impl From<Bytes> for BulkString {
+    fn from(data: Bytes) -> Self {
+        BulkString(data)
     }
 }
 
@@ -42,11 +55,14 @@ impl RespDecode for BulkString {
         if remained.len() < len + CRLF_LEN {
             return Err(RespError::NotComplete);
         }
+        check_bulk_end(remained, len)?;
 
         buf.advance(end + CRLF_LEN);
 
-        let data = buf.split_to(len + CRLF_LEN);
-        Ok(BulkString::new(data[..len].to_vec()))
+        // split the payload off the read buffer instead of copying it
+        let mut data = buf.split_to(len + CRLF_LEN);
+        data.truncate(len);
+        Ok(BulkString(data.freeze()))
     }
 
     fn expect_length(buf: &[u8]) -> Result<usize, RespError> {
@@ -56,17 +72,19 @@ impl RespDecode for BulkString {
 }
 
 impl RespEncode for BulkString {
-    fn encode(self) -> Vec<u8> {
-        let mut buf = Vec::with_capacity(self.len() + 16);
-        buf.extend_from_slice(&format!("${}\r\n", self.len()).into_bytes());
-        buf.extend_from_slice(&self);
-        buf.extend_from_slice(b"\r\n");
-        buf
+    fn encode_to<B: BufMut>(&self, buf: &mut B) {
+        put_length_header(buf, b'$', self.len());
+        buf.put_slice(self);
+        buf.put_slice(CRLF);
+    }
+
+    fn encoded_len(&self) -> usize {
+        length_header_len(self.len()) + self.len() + CRLF_LEN
     }
 }
 
 impl Deref for BulkString {
-    type Target = Vec<u8>;
+    type Target = Bytes;
     fn deref(&self) -> &Self::Target {
         &self.0
     }
@@ -110,6 +128,30 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_bulk_string_decode_is_zero_copy() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"$5\r\nhello\r\n");
+        let payload = buf[4..].as_ptr();
+
+        let frame = BulkString::decode(&mut buf)?;
+        assert_eq!(frame.as_ptr(), payload);
+        assert_eq!(frame, BulkString::from(Bytes::from_static(b"hello")));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_bulk_string_decode_without_crlf() {
+        let mut buf = BytesMut::from(&b"$3\r\nabcXY:1\r\n"[..]);
+        let ret = BulkString::decode(&mut buf);
+        assert!(matches!(ret.unwrap_err(), RespError::InvalidFrame(_)));
+
+        let mut buf = BytesMut::from(&b"*2\r\n$3\r\nabcXY:1\r\n"[..]);
+        let ret = RespFrame::decode(&mut buf);
+        assert!(matches!(ret.unwrap_err(), RespError::InvalidFrame(_)));
+    }
+
     #[test]
     fn test_null_bulk_string_decode() -> Result<()> {
         let mut buf = BytesMut::new();
@@ -0,0 +1,143 @@
+use std::ops::Deref;
+
+use bytes::{BufMut, BytesMut};
+
+use crate::{
+    resp::{
+        length_header_len,
+        parser::{decode_frame, frame_len},
+        put_length_header, RespDecode, RespEncode, RespError, RespLimits,
+    },
+    RespFrame,
+};
+
+#[derive(Debug, PartialEq, PartialOrd)]
+pub struct RespMap(pub Vec<(RespFrame, RespFrame)>);
+
+impl RespDecode for RespMap {
+    const PREFIX: &'static str = "%";
+
+    fn decode(buf: &mut BytesMut) -> Result<Self, RespError> {
+        match decode_frame(buf, Self::PREFIX, &RespLimits::default())? {
+            RespFrame::Map(frame) => Ok(frame),
+            frame => Err(RespError::InvalidFrame(format!(
+                "Expect RespMap but got {:?}",
+                frame
+            ))),
+        }
+    }
+
+    fn expect_length(buf: &[u8]) -> Result<usize, RespError> {
+        frame_len(buf, Self::PREFIX, &RespLimits::default())
+    }
+}
+
+impl RespEncode for RespMap {
+    fn encode_to<B: BufMut>(&self, buf: &mut B) {
+        put_length_header(buf, b'%', self.0.len());
+        for (key, value) in &self.0 {
+            key.encode_to(buf);
+            value.encode_to(buf);
+        }
+    }
+
+    fn encoded_len(&self) -> usize {
+        length_header_len(self.0.len())
+            + self
+                .0
+                .iter()
+                .map(|(key, value)| key.encoded_len() + value.encoded_len())
+                .sum::<usize>()
+    }
+}
+
+impl RespMap {
+    pub fn new(pairs: impl Into<Vec<(RespFrame, RespFrame)>>) -> Self {
+        RespMap(pairs.into())
+    }
+}
+
+impl Deref for RespMap {
+    type Target = Vec<(RespFrame, RespFrame)>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<Vec<(RespFrame, RespFrame)>> for RespMap {
+    fn from(value: Vec<(RespFrame, RespFrame)>) -> Self {
+        RespMap::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::resp::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_map_encode() {
+        let frame: RespFrame = RespMap::new(vec![
+            (
+                SimpleString::new("hello").into(),
+                BulkString::new("world").into(),
+            ),
+            (SimpleString::new("foo").into(), (-123456.789).into()),
+        ])
+        .into();
+        assert_eq!(
+            &frame.encode(),
+            b"%2\r\n+hello\r\n$5\r\nworld\r\n+foo\r\n,-123456.789\r\n"
+        );
+    }
+
+    #[test]
+    fn test_map_decode() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"%2\r\n+hello\r\n$5\r\nworld\r\n:1\r\n#t\r\n");
+
+        let frame = RespMap::decode(&mut buf)?;
+        assert_eq!(
+            frame,
+            RespMap::new(vec![
+                (SimpleString::new("hello").into(), b"world".into()),
+                (1.into(), true.into()),
+            ])
+        );
+        assert!(buf.is_empty());
+
+        buf.extend_from_slice(b"%1\r\n+hello\r\n");
+        let ret = RespMap::decode(&mut buf);
+        assert_eq!(ret.unwrap_err(), RespError::NotComplete);
+
+        buf.extend_from_slice(b"$5\r\nworld\r\n");
+        let frame = RespMap::decode(&mut buf)?;
+        assert_eq!(
+            frame,
+            RespMap::new(vec![(SimpleString::new("hello").into(), b"world".into())])
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_nested_map_decode() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*1\r\n%1\r\n*1\r\n:1\r\n%0\r\n");
+
+        let frame = RespFrame::decode(&mut buf)?;
+        assert_eq!(
+            frame,
+            RespFrameArray::new(vec![RespMap::new(vec![(
+                RespFrameArray::new(vec![1.into()]).into(),
+                RespMap::new(vec![]).into(),
+            )])
+            .into()])
+            .into()
+        );
+
+        Ok(())
+    }
+}
@@ -1,5 +1,17 @@
 mod arrays;
+mod attributes;
+mod bulk_errors;
 mod bulk_strings;
+mod maps;
+mod pushes;
+mod sets;
+mod verbatim_strings;
 
 pub use arrays::{RespFrameArray, RespNullArray};
+pub use attributes::RespAttribute;
+pub use bulk_errors::BulkError;
 pub use bulk_strings::{BulkNullString, BulkString};
+pub use maps::RespMap;
+pub use pushes::RespPush;
+pub use sets::RespSet;
+pub use verbatim_strings::VerbatimString;
@@ -0,0 +1,131 @@
+use std::ops::Deref;
+
+use bytes::{BufMut, BytesMut};
+
+use crate::{
+    resp::{
+        length_header_len,
+        parser::{decode_frame, frame_len},
+        put_length_header, RespDecode, RespEncode, RespError, RespLimits,
+    },
+    RespFrame,
+};
+
+/// An out-of-band RESP3 push message, e.g. a pub/sub message or a client-side
+/// caching invalidation. It has the same shape as an array but is never a reply.
+#[derive(Debug, PartialEq, PartialOrd)]
+pub struct RespPush(pub Vec<RespFrame>);
+
+impl RespDecode for RespPush {
+    const PREFIX: &'static str = ">";
+
+    fn decode(buf: &mut BytesMut) -> Result<Self, RespError> {
+        match decode_frame(buf, Self::PREFIX, &RespLimits::default())? {
+            RespFrame::Push(frame) => Ok(frame),
+            frame => Err(RespError::InvalidFrame(format!(
+                "Expect RespPush but got {:?}",
+                frame
+            ))),
+        }
+    }
+
+    fn expect_length(buf: &[u8]) -> Result<usize, RespError> {
+        frame_len(buf, Self::PREFIX, &RespLimits::default())
+    }
+}
+
+impl RespEncode for RespPush {
+    fn encode_to<B: BufMut>(&self, buf: &mut B) {
+        put_length_header(buf, b'>', self.0.len());
+        for frame in &self.0 {
+            frame.encode_to(buf);
+        }
+    }
+
+    fn encoded_len(&self) -> usize {
+        length_header_len(self.0.len()) + self.0.iter().map(|f| f.encoded_len()).sum::<usize>()
+    }
+}
+
+impl RespPush {
+    pub fn new(s: impl Into<Vec<RespFrame>>) -> Self {
+        RespPush(s.into())
+    }
+
+    /// The push kind, e.g. `message` or `invalidate`, taken from the first element.
+    pub fn kind(&self) -> Option<&[u8]> {
+        match self.0.first()? {
+            RespFrame::BulkStrings(s) => Some(&s[..]),
+            RespFrame::Strings(s) => Some(s.as_bytes()),
+            _ => None,
+        }
+    }
+}
+
+impl Deref for RespPush {
+    type Target = Vec<RespFrame>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<Vec<RespFrame>> for RespPush {
+    fn from(value: Vec<RespFrame>) -> Self {
+        RespPush::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::resp::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_push_encode() {
+        let frame: RespFrame =
+            RespPush::new(vec![b"message".into(), b"channel".into(), b"hello".into()]).into();
+        assert_eq!(
+            &frame.encode(),
+            b">3\r\n$7\r\nmessage\r\n$7\r\nchannel\r\n$5\r\nhello\r\n"
+        );
+    }
+
+    #[test]
+    fn test_push_decode() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b">2\r\n$10\r\ninvalidate\r\n*1\r\n$3\r\nfoo\r\n");
+
+        let frame = RespPush::decode(&mut buf)?;
+        assert_eq!(frame.kind(), Some(&b"invalidate"[..]));
+        assert_eq!(
+            frame,
+            RespPush::new([
+                b"invalidate".into(),
+                RespFrameArray::new([b"foo".into()]).into()
+            ])
+        );
+
+        buf.extend_from_slice(b">2\r\n$7\r\nmessage\r\n");
+        let ret = RespPush::decode(&mut buf);
+        assert_eq!(ret.unwrap_err(), RespError::NotComplete);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_push_interleaved_with_replies() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b">1\r\n$4\r\npush\r\n*1\r\n$5\r\nreply\r\n");
+
+        let frame = RespFrame::decode(&mut buf)?;
+        assert_eq!(frame, RespPush::new([b"push".into()]).into());
+
+        let frame = RespFrame::decode(&mut buf)?;
+        assert_eq!(frame, RespFrameArray::new([b"reply".into()]).into());
+        assert!(buf.is_empty());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,266 @@
+use std::{
+    cmp::Ordering,
+    collections::{hash_map::RandomState, HashMap},
+    hash::{BuildHasher, Hash, Hasher},
+    mem,
+    ops::Deref,
+};
+
+use bytes::{BufMut, BytesMut};
+
+use crate::{
+    resp::{
+        length_header_len,
+        parser::{decode_frame, frame_len},
+        put_length_header, RespDecode, RespEncode, RespError, RespLimits,
+    },
+    RespFrame,
+};
+
+/// A RESP3 set. Elements keep their first-seen order and duplicates are dropped,
+/// both when the set is built locally and when it is decoded from the wire.
+#[derive(Debug)]
+pub struct RespSet {
+    frames: Vec<RespFrame>,
+    // the positions of the frames by hash, so a frame is only compared with the frames
+    // it collides with; randomly seeded, so peers can't pick colliding elements
+    index: HashMap<u64, Vec<usize>>,
+    hasher: RandomState,
+}
+
+impl RespDecode for RespSet {
+    const PREFIX: &'static str = "~";
+
+    fn decode(buf: &mut BytesMut) -> Result<Self, RespError> {
+        match decode_frame(buf, Self::PREFIX, &RespLimits::default())? {
+            RespFrame::Set(frame) => Ok(frame),
+            frame => Err(RespError::InvalidFrame(format!(
+                "Expect RespSet but got {:?}",
+                frame
+            ))),
+        }
+    }
+
+    fn expect_length(buf: &[u8]) -> Result<usize, RespError> {
+        frame_len(buf, Self::PREFIX, &RespLimits::default())
+    }
+}
+
+impl RespEncode for RespSet {
+    fn encode_to<B: BufMut>(&self, buf: &mut B) {
+        put_length_header(buf, b'~', self.frames.len());
+        for frame in &self.frames {
+            frame.encode_to(buf);
+        }
+    }
+
+    fn encoded_len(&self) -> usize {
+        length_header_len(self.frames.len())
+            + self.frames.iter().map(|f| f.encoded_len()).sum::<usize>()
+    }
+}
+
+impl RespSet {
+    pub fn new(s: impl Into<Vec<RespFrame>>) -> Self {
+        let frames = s.into();
+        let mut set = RespSet {
+            frames: Vec::with_capacity(frames.len()),
+            index: HashMap::with_capacity(frames.len()),
+            hasher: RandomState::new(),
+        };
+        for frame in frames {
+            set.insert(frame);
+        }
+        set
+    }
+
+    /// Adds a frame to the set, returning `false` if an equal frame is already present.
+    pub fn insert(&mut self, frame: RespFrame) -> bool {
+        let mut hasher = self.hasher.build_hasher();
+        hash_frame(&frame, &mut hasher);
+        let positions = self.index.entry(hasher.finish()).or_default();
+        if positions.iter().any(|&i| self.frames[i] == frame) {
+            return false;
+        }
+        positions.push(self.frames.len());
+        self.frames.push(frame);
+        true
+    }
+}
+
+// Hashes `frame` consistently with its `PartialEq`: frames that compare equal hash equally,
+// e.g. `0.0` and `-0.0`, or big numbers differing only in trailing zeros.
+fn hash_frame<H: Hasher>(frame: &RespFrame, state: &mut H) {
+    mem::discriminant(frame).hash(state);
+    match frame {
+        RespFrame::Strings(s) => s.0.hash(state),
+        RespFrame::Errors(e) => e.0.hash(state),
+        RespFrame::Integers(i) => i.hash(state),
+        RespFrame::Booleans(b) => b.hash(state),
+        RespFrame::Doubles(f) => match *f == 0.0 {
+            true => 0u64.hash(state),
+            false => f.to_bits().hash(state),
+        },
+        RespFrame::BigNumbers(num) => num.0.hash(state),
+        RespFrame::BulkStrings(s) => s.0.hash(state),
+        RespFrame::BulkErrors(e) => e.0.hash(state),
+        RespFrame::VerbatimStrings(v) => {
+            v.format().hash(state);
+            v.as_slice().hash(state);
+        }
+        RespFrame::Array(frames) => hash_frames(frames.iter(), state),
+        RespFrame::Set(frames) => hash_frames(frames.iter(), state),
+        RespFrame::Push(frames) => hash_frames(frames.iter(), state),
+        RespFrame::Map(pairs) => hash_pairs(pairs, state),
+        RespFrame::Attribute(attribute) => {
+            hash_pairs(&attribute.attributes, state);
+            hash_frame(&attribute.frame, state);
+        }
+        RespFrame::Nulls(_) | RespFrame::BulkNullStrings(_) | RespFrame::NullArray(_) => {}
+    }
+}
+
+fn hash_frames<'a, H: Hasher>(frames: impl ExactSizeIterator<Item = &'a RespFrame>, state: &mut H) {
+    frames.len().hash(state);
+    for frame in frames {
+        hash_frame(frame, state);
+    }
+}
+
+fn hash_pairs<H: Hasher>(pairs: &[(RespFrame, RespFrame)], state: &mut H) {
+    pairs.len().hash(state);
+    for (key, value) in pairs {
+        hash_frame(key, state);
+        hash_frame(value, state);
+    }
+}
+
+// sets compare by their elements in order, the index is only a lookup aid
+impl PartialEq for RespSet {
+    fn eq(&self, other: &Self) -> bool {
+        self.frames == other.frames
+    }
+}
+
+impl PartialOrd for RespSet {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.frames.partial_cmp(&other.frames)
+    }
+}
+
+impl IntoIterator for RespSet {
+    type Item = RespFrame;
+    type IntoIter = std::vec::IntoIter<RespFrame>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.frames.into_iter()
+    }
+}
+
+impl Deref for RespSet {
+    type Target = Vec<RespFrame>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.frames
+    }
+}
+
+impl From<Vec<RespFrame>> for RespSet {
+    fn from(value: Vec<RespFrame>) -> Self {
+        RespSet::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::resp::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_set_encode() {
+        let frame: RespFrame = RespSet::new(vec![
+            RespFrameArray::new([1234.into(), true.into()]).into(),
+            BulkString::new("world").into(),
+        ])
+        .into();
+        assert_eq!(
+            &frame.encode(),
+            b"~2\r\n*2\r\n:+1234\r\n#t\r\n$5\r\nworld\r\n"
+        );
+    }
+
+    #[test]
+    fn test_set_new_drops_duplicates() -> Result<()> {
+        let set = RespSet::new(vec![b"a".into(), b"b".into(), b"a".into()]);
+        assert_eq!(set.len(), 2);
+        assert_eq!(set.encode(), b"~2\r\n$1\r\na\r\n$1\r\nb\r\n");
+
+        let mut set = RespSet::new(vec![]);
+        assert!(set.insert(1.into()));
+        assert!(!set.insert(1.into()));
+        assert_eq!(set.len(), 1);
+
+        let set = RespSet::new(vec![0.0.into(), (-0.0).into(), f64::NAN.into()]);
+        assert_eq!(set.len(), 2);
+        let set = RespSet::new(vec![
+            BigNumber::new("1.50")?.into(),
+            BigNumber::new("1.5")?.into(),
+            RespFrameArray::new(vec![1.into()]).into(),
+            RespFrameArray::new(vec![1.into()]).into(),
+        ]);
+        assert_eq!(set.len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_set_decode_large() -> Result<()> {
+        // a quadratic duplicate check would take billions of comparisons here
+        let n = 200_000;
+        let mut buf = BytesMut::from(format!("~{}\r\n", n + 1).as_bytes());
+        for i in 0..n {
+            buf.extend_from_slice(format!(":{}\r\n", i).as_bytes());
+        }
+        buf.extend_from_slice(b":0\r\n");
+
+        let start = std::time::Instant::now();
+        let RespFrame::Set(set) = RespFrame::decode(&mut buf)? else {
+            panic!("expected a set");
+        };
+        assert!(start.elapsed() < std::time::Duration::from_secs(5));
+        assert_eq!(set.len(), n);
+        assert_eq!(set[n - 1], RespFrame::from(n as i64 - 1));
+        Ok(())
+    }
+
+    #[test]
+    fn test_set_decode() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"~2\r\n$3\r\nset\r\n$5\r\nhello\r\n");
+
+        let frame = RespSet::decode(&mut buf)?;
+        assert_eq!(frame, RespSet::new([b"set".into(), b"hello".into()]));
+
+        buf.extend_from_slice(b"~2\r\n$3\r\nset\r\n");
+        let ret = RespSet::decode(&mut buf);
+        assert_eq!(ret.unwrap_err(), RespError::NotComplete);
+
+        buf.extend_from_slice(b"$5\r\nhello\r\n");
+        let frame = RespSet::decode(&mut buf)?;
+        assert_eq!(frame, RespSet::new([b"set".into(), b"hello".into()]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_set_decode_duplicates() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"~3\r\n:1\r\n:2\r\n:1\r\n+next\r\n");
+
+        let frame = RespFrame::decode(&mut buf)?;
+        assert_eq!(frame, RespSet::new([1.into(), 2.into()]).into());
+        assert_eq!(buf.as_ref(), b"+next\r\n");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,178 @@
+use std::ops::Deref;
+
+use bytes::{Buf, BufMut, BytesMut};
+
+use crate::resp::{
+    check_bulk_end, length_header_len, parse_length, put_length_header, RespDecode, RespEncode,
+    RespError, CRLF, CRLF_LEN,
+};
+
+const FORMAT_LEN: usize = 3;
+
+#[derive(Debug, PartialEq, PartialOrd)]
+pub struct VerbatimString {
+    format: [u8; FORMAT_LEN],
+    data: Vec<u8>,
+}
+
+impl VerbatimString {
+    pub fn new(format: &str, data: impl Into<Vec<u8>>) -> Result<VerbatimString, RespError> {
+        let format = parse_format(format.as_bytes())?;
+        Ok(VerbatimString {
+            format,
+            data: data.into(),
+        })
+    }
+
+    pub fn txt(data: impl Into<Vec<u8>>) -> VerbatimString {
+        VerbatimString {
+            format: *b"txt",
+            data: data.into(),
+        }
+    }
+
+    pub fn mkd(data: impl Into<Vec<u8>>) -> VerbatimString {
+        VerbatimString {
+            format: *b"mkd",
+            data: data.into(),
+        }
+    }
+
+    pub fn format(&self) -> &str {
+        // the format is validated to be ascii on construction
+        std::str::from_utf8(&self.format).unwrap_or_default()
+    }
+}
+
+fn parse_format(format: &[u8]) -> Result<[u8; FORMAT_LEN], RespError> {
+    let valid =
+        format.len() == FORMAT_LEN && format.iter().all(|b| b.is_ascii_graphic() && *b != b':');
+    if !valid {
+        return Err(RespError::InvalidFrame(format!(
+            "Invalid verbatim string format: {:?}",
+            String::from_utf8_lossy(format)
+        )));
+    }
+    let mut ret = [0; FORMAT_LEN];
+    ret.copy_from_slice(format);
+    Ok(ret)
+}
+
+impl RespDecode for VerbatimString {
+    const PREFIX: &'static str = "=";
+    fn decode(buf: &mut BytesMut) -> Result<Self, RespError> {
+        let (end, len) = parse_length(buf, Self::PREFIX)?;
+        let remained = &buf[end + CRLF_LEN..];
+        if remained.len() < len + CRLF_LEN {
+            return Err(RespError::NotComplete);
+        }
+        check_bulk_end(remained, len)?;
+        if len <= FORMAT_LEN || remained[FORMAT_LEN] != b':' {
+            return Err(RespError::InvalidFrame(format!(
+                "Verbatim string must start with a 3-byte format and ':', got {:?}",
+                String::from_utf8_lossy(&remained[..len])
+            )));
+        }
+        let format = parse_format(&remained[..FORMAT_LEN])?;
+
+        buf.advance(end + CRLF_LEN);
+
+        let data = buf.split_to(len + CRLF_LEN);
+        Ok(VerbatimString {
+            format,
+            data: data[FORMAT_LEN + 1..len].to_vec(),
+        })
+    }
+
+    fn expect_length(buf: &[u8]) -> Result<usize, RespError> {
+        let (end, len) = parse_length(buf, Self::PREFIX)?;
+        Ok(end + CRLF_LEN + len + CRLF_LEN)
+    }
+}
+
+impl RespEncode for VerbatimString {
+    fn encode_to<B: BufMut>(&self, buf: &mut B) {
+        put_length_header(buf, b'=', FORMAT_LEN + 1 + self.data.len());
+        buf.put_slice(&self.format);
+        buf.put_u8(b':');
+        buf.put_slice(&self.data);
+        buf.put_slice(CRLF);
+    }
+
+    fn encoded_len(&self) -> usize {
+        let len = FORMAT_LEN + 1 + self.data.len();
+        length_header_len(len) + len + CRLF_LEN
+    }
+}
+
+impl Deref for VerbatimString {
+    type Target = Vec<u8>;
+    fn deref(&self) -> &Self::Target {
+        &self.data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RespFrame;
+
+    use super::*;
+    use anyhow::Result;
+
+    #[test]
+    fn test_verbatim_string_encode() -> Result<()> {
+        let frame: RespFrame = VerbatimString::txt("Some string").into();
+        assert_eq!(frame.encode(), b"=15\r\ntxt:Some string\r\n");
+
+        let frame: RespFrame = VerbatimString::new("mkd", "# title")?.into();
+        assert_eq!(frame.encode(), b"=11\r\nmkd:# title\r\n");
+
+        assert!(VerbatimString::new("text", "hello").is_err());
+        assert!(VerbatimString::new("t:t", "hello").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_verbatim_string_decode() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"=15\r\ntxt:Some string\r\n");
+
+        let frame = VerbatimString::decode(&mut buf)?;
+        assert_eq!(frame.format(), "txt");
+        assert_eq!(frame, VerbatimString::txt("Some string"));
+
+        buf.extend_from_slice(b"=4\r\nmkd:");
+        let ret = VerbatimString::decode(&mut buf);
+        assert_eq!(ret.unwrap_err(), RespError::NotComplete);
+
+        buf.extend_from_slice(b"\r\n");
+        let frame = VerbatimString::decode(&mut buf)?;
+        assert_eq!(frame, VerbatimString::mkd(""));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_verbatim_string_decode_malformed_format() {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"=11\r\ntext:string\r\n");
+        let ret = VerbatimString::decode(&mut buf);
+        assert!(matches!(ret.unwrap_err(), RespError::InvalidFrame(_)));
+
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"=3\r\ntxt\r\n");
+        let ret = VerbatimString::decode(&mut buf);
+        assert!(matches!(ret.unwrap_err(), RespError::InvalidFrame(_)));
+    }
+
+    #[test]
+    fn test_verbatim_string_decode_without_crlf() {
+        let mut buf = BytesMut::from(&b"=7\r\ntxt:abcXY:1\r\n"[..]);
+        let ret = VerbatimString::decode(&mut buf);
+        assert!(matches!(ret.unwrap_err(), RespError::InvalidFrame(_)));
+
+        let mut buf = BytesMut::from(&b"*2\r\n=7\r\ntxt:abcXY:1\r\n"[..]);
+        let ret = RespFrame::decode(&mut buf);
+        assert!(matches!(ret.unwrap_err(), RespError::InvalidFrame(_)));
+    }
+}
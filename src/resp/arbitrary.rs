@@ -0,0 +1,153 @@
+//! A proptest [`Arbitrary`] generator for [`RespFrame`] trees and the codec properties
+//! every frame must hold.
+
+use bigdecimal::BigDecimal;
+use bytes::BytesMut;
+use proptest::{collection::vec, prelude::*, strategy::LazyJust};
+
+use crate::resp::{
+    BigNumber, BulkError, BulkNullString, BulkString, RespAttribute, RespDecode, RespEncode,
+    RespError, RespFrame, RespFrameArray, RespLimits, RespMap, RespNullArray, RespParser, RespPush,
+    RespSet, SimpleError, SimpleNull, SimpleString, VerbatimString,
+};
+
+impl Arbitrary for RespFrame {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<RespFrame>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        scalar_frame()
+            .prop_recursive(4, 64, 8, |inner| {
+                prop_oneof![
+                    vec(inner.clone(), 0..8).prop_map(|frames| RespFrameArray::new(frames).into()),
+                    vec(inner.clone(), 0..8).prop_map(|frames| RespSet::new(frames).into()),
+                    vec(inner.clone(), 0..8).prop_map(|frames| RespPush::new(frames).into()),
+                    vec((inner.clone(), inner.clone()), 0..4)
+                        .prop_map(|pairs| RespMap::new(pairs).into()),
+                    (vec((inner.clone(), inner.clone()), 0..4), inner).prop_map(
+                        |(pairs, frame)| RespAttribute::new(RespMap::new(pairs), frame).into()
+                    ),
+                ]
+            })
+            .boxed()
+    }
+}
+
+fn scalar_frame() -> impl Strategy<Value = RespFrame> {
+    // NaN is left out as it never equals itself, simple strings can't span lines
+    prop_oneof![
+        "[^\r\n]*".prop_map(|s| SimpleString::new(s).into()),
+        "[^\r\n]*".prop_map(|s| SimpleError::new(s).into()),
+        any::<i64>().prop_map(RespFrame::from),
+        LazyJust::new(|| SimpleNull.into()),
+        any::<bool>().prop_map(RespFrame::from),
+        prop_oneof![
+            any::<f64>().prop_filter("NaN", |f| !f.is_nan()),
+            Just(f64::INFINITY),
+            Just(f64::NEG_INFINITY),
+            Just(-0.0),
+        ]
+        .prop_map(RespFrame::from),
+        // the prefix properties decode every partial line again, so in frames keep the
+        // numbers short, `test_big_number_round_trip` covers every accepted scale
+        (any::<i128>(), scale_of_bits(10)).prop_map(|(digits, scale)| big_number(digits, scale)),
+        vec(any::<u8>(), 0..64).prop_map(|data| BulkString::new(data).into()),
+        LazyJust::new(|| BulkNullString.into()),
+        vec(any::<u8>(), 0..64).prop_map(|data| BulkError::new(data).into()),
+        vec(any::<u8>(), 0..64).prop_map(|data| VerbatimString::txt(data).into()),
+        LazyJust::new(|| RespNullArray.into()),
+    ]
+}
+
+// Every scale the decoder accepts, mostly short ones, and the largest on either side.
+fn big_number_scale() -> impl Strategy<Value = i64> {
+    // an i128 has up to 39 digits, followed by as many zeros as the scale is negative
+    let limit = RespLimits::default().max_big_number_len as i64;
+    let (min, max) = (-(limit - 39), limit - 2);
+    prop_oneof![
+        6 => scale_of_bits(limit.ilog2() + 1).prop_map(move |scale| scale.clamp(min, max)),
+        1 => Just(min),
+        1 => Just(max),
+    ]
+}
+
+// each bit length up to `max_bits` is equally likely, so small scales stay common
+fn scale_of_bits(max_bits: u32) -> impl Strategy<Value = i64> {
+    (0..=max_bits).prop_flat_map(|bits| {
+        let bound = 1i64 << bits;
+        -bound..=bound
+    })
+}
+
+fn big_number(digits: i128, scale: i64) -> RespFrame {
+    BigNumber(BigDecimal::new(digits.into(), scale)).into()
+}
+
+proptest! {
+    #[test]
+    fn test_frame_round_trip(frame: RespFrame) {
+        let data = encode_ref(&frame);
+        prop_assert_eq!(frame.encoded_len(), data.len());
+
+        let mut buf = BytesMut::from(&data[..]);
+        prop_assert_eq!(RespFrame::decode(&mut buf)?, frame);
+        prop_assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn test_frame_prefix_is_not_complete(frame: RespFrame) {
+        let data = encode_ref(&frame);
+        for len in 0..data.len() {
+            let mut buf = BytesMut::from(&data[..len]);
+            prop_assert_eq!(RespFrame::decode(&mut buf), Err(RespError::NotComplete));
+            prop_assert_eq!(buf.len(), len);
+        }
+    }
+
+    #[test]
+    fn test_parser_byte_by_byte(frame: RespFrame) {
+        let data = encode_ref(&frame);
+        let mut parser = RespParser::new();
+        let mut buf = BytesMut::new();
+        for &b in &data[..data.len() - 1] {
+            buf.extend_from_slice(&[b]);
+            prop_assert_eq!(parser.parse(&mut buf), Err(RespError::NotComplete));
+        }
+        buf.extend_from_slice(&data[data.len() - 1..]);
+        prop_assert_eq!(parser.parse(&mut buf)?, frame);
+    }
+
+    #[test]
+    fn test_big_number_round_trip(digits: i128, scale in big_number_scale()) {
+        let frame = big_number(digits, scale);
+        let data = encode_ref(&frame);
+        let mut buf = BytesMut::from(&data[..]);
+        prop_assert_eq!(RespFrame::decode(&mut buf)?, frame);
+
+        // a number at the limit must still fit in a line while it arrives
+        let mut buf = BytesMut::from(&data[..data.len() - 1]);
+        prop_assert_eq!(RespFrame::decode(&mut buf), Err(RespError::NotComplete));
+        prop_assert_eq!(RespParser::new().parse(&mut buf), Err(RespError::NotComplete));
+    }
+
+    #[test]
+    fn test_big_number_any_exponent(digits: i128, exponent: i64) {
+        let data = format!("({}e{}\r\n", digits, exponent);
+        let mut buf = BytesMut::from(data.as_bytes());
+        match RespFrame::decode(&mut buf) {
+            Ok(frame) => {
+                let len = RespLimits::default().max_big_number_len;
+                prop_assert!(frame.encoded_len() <= len + 4);
+                prop_assert_eq!(frame.encoded_len(), encode_ref(&frame).len());
+            }
+            Err(e) => prop_assert!(matches!(e, RespError::BigNumberTooLong(_)), "{:?}", e),
+        }
+    }
+}
+
+// `encode` takes the frame by value, the properties still need it for comparison
+fn encode_ref(frame: &RespFrame) -> Vec<u8> {
+    let mut buf = Vec::with_capacity(frame.encoded_len());
+    frame.encode_to(&mut buf);
+    buf
+}
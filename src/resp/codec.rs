@@ -0,0 +1,225 @@
+use bytes::BytesMut;
+use thiserror::Error;
+use tokio_util::codec::{Decoder, Encoder};
+
+use crate::resp::{RespError, RespFrame, RespLimits, RespParser, RespVersion};
+
+/// A `tokio_util` codec for reading and writing [`RespFrame`]s, e.g. with `Framed`.
+#[derive(Debug, Default)]
+pub struct RespCodec {
+    parser: RespParser,
+    version: RespVersion,
+}
+
+#[derive(Error, Debug)]
+pub enum RespCodecError {
+    #[error("Io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Resp(#[from] RespError),
+}
+
+impl RespCodec {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_limits(limits: RespLimits) -> Self {
+        RespCodec {
+            parser: RespParser::with_limits(limits),
+            ..Default::default()
+        }
+    }
+
+    pub fn version(&self) -> RespVersion {
+        self.version
+    }
+
+    /// Switches the protocol frames are encoded with, e.g. after the client sent `HELLO`.
+    pub fn set_version(&mut self, version: RespVersion) {
+        self.version = version;
+    }
+}
+
+impl Decoder for RespCodec {
+    type Item = RespFrame;
+    type Error = RespCodecError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        match self.parser.parse(src) {
+            Ok(frame) => Ok(Some(frame)),
+            Err(RespError::NotComplete) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn decode_eof(&mut self, buf: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        match self.decode(buf)? {
+            Some(frame) => Ok(Some(frame)),
+            // the peer closed the connection in the middle of a frame
+            None if self.parser.is_partial() || !buf.is_empty() => Err(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                "connection closed with an incomplete frame",
+            )
+            .into()),
+            None => Ok(None),
+        }
+    }
+}
+
+impl Encoder<RespFrame> for RespCodec {
+    type Error = RespCodecError;
+
+    fn encode(&mut self, item: RespFrame, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        dst.reserve(item.encoded_len_with_version(self.version));
+        item.encode_to_with_version(dst, self.version);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::resp::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+    use futures::{SinkExt, StreamExt};
+    use tokio::io::AsyncWriteExt;
+    use tokio_util::codec::{Decoder, Encoder, Framed, FramedRead};
+
+    #[test]
+    fn test_codec_decode() -> Result<()> {
+        let mut codec = RespCodec::new();
+        let mut buf = BytesMut::new();
+
+        buf.extend_from_slice(b"*2\r\n$3\r\nget\r\n$5\r\nhel");
+        assert!(codec.decode(&mut buf)?.is_none());
+
+        buf.extend_from_slice(b"lo\r\n+OK\r\n");
+        let frame = codec.decode(&mut buf)?;
+        assert_eq!(
+            frame,
+            Some(RespFrameArray::new([b"get".into(), b"hello".into()]).into())
+        );
+        let frame = codec.decode(&mut buf)?;
+        assert_eq!(frame, Some(SimpleString::new("OK").into()));
+        assert!(codec.decode(&mut buf)?.is_none());
+
+        buf.extend_from_slice(b"?\r\n");
+        let ret = codec.decode(&mut buf);
+        assert!(matches!(
+            ret.unwrap_err(),
+            RespCodecError::Resp(RespError::InvalidFrameType(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_codec_encode() -> Result<()> {
+        let mut codec = RespCodec::new();
+        let mut buf = BytesMut::new();
+
+        codec.encode(SimpleString::new("OK").into(), &mut buf)?;
+        codec.encode(BulkString::new("hello").into(), &mut buf)?;
+        assert_eq!(&buf[..], b"+OK\r\n$5\r\nhello\r\n");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_codec_encode_resp2() -> Result<()> {
+        let mut codec = RespCodec::new();
+        let mut buf = BytesMut::new();
+
+        codec.set_version(RespVersion::Resp2);
+        assert_eq!(codec.version(), RespVersion::Resp2);
+        codec.encode(true.into(), &mut buf)?;
+        codec.encode(SimpleNull.into(), &mut buf)?;
+        assert_eq!(&buf[..], b":1\r\n$-1\r\n");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_codec_framed() -> Result<()> {
+        let (client, server) = tokio::io::duplex(64);
+        let mut client = Framed::new(client, RespCodec::new());
+        let mut server = Framed::new(server, RespCodec::new());
+
+        client
+            .send(RespFrameArray::new([b"ping".into()]).into())
+            .await?;
+        let request = server.next().await.transpose()?;
+        assert_eq!(request, Some(RespFrameArray::new([b"ping".into()]).into()));
+
+        server.send(SimpleString::new("PONG").into()).await?;
+        let reply = client.next().await.transpose()?;
+        assert_eq!(reply, Some(SimpleString::new("PONG").into()));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_codec_reads_frames_split_across_writes() -> Result<()> {
+        let (mut writer, reader) = tokio::io::duplex(64);
+        let mut reader = FramedRead::new(reader, RespCodec::new());
+
+        tokio::spawn(async move {
+            for chunk in [&b"*2\r\n$3\r\nse"[..], b"t\r\n$1", b"\r\na\r\n:1\r\n"] {
+                writer.write_all(chunk).await?;
+                writer.flush().await?;
+                tokio::task::yield_now().await;
+            }
+            anyhow::Ok(())
+        });
+
+        let frame = reader.next().await.transpose()?;
+        assert_eq!(
+            frame,
+            Some(RespFrameArray::new([b"set".into(), b"a".into()]).into())
+        );
+        let frame = reader.next().await.transpose()?;
+        assert_eq!(frame, Some(1.into()));
+        assert!(reader.next().await.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_codec_decode_eof() -> Result<()> {
+        let mut codec = RespCodec::new();
+        let mut buf = BytesMut::from(&b"+OK\r\n"[..]);
+        assert_eq!(
+            codec.decode_eof(&mut buf)?,
+            Some(SimpleString::new("OK").into())
+        );
+        assert!(codec.decode_eof(&mut buf)?.is_none());
+
+        // the array header is consumed, so only the parser knows a frame was started
+        let mut buf = BytesMut::from(&b"*2\r\n$1\r\na\r\n"[..]);
+        let ret = codec.decode_eof(&mut buf);
+        assert!(buf.is_empty());
+        assert!(matches!(
+            ret.unwrap_err(),
+            RespCodecError::Io(e) if e.kind() == std::io::ErrorKind::UnexpectedEof
+        ));
+
+        let mut codec = RespCodec::new();
+        let mut buf = BytesMut::from(&b"$5\r\nhel"[..]);
+        assert!(codec.decode_eof(&mut buf).is_err());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_codec_stream_ends_mid_frame() -> Result<()> {
+        let (mut writer, reader) = tokio::io::duplex(64);
+        let mut reader = FramedRead::new(reader, RespCodec::new());
+
+        writer.write_all(b"*2\r\n$3\r\nget\r\n").await?;
+        drop(writer);
+        assert!(reader.next().await.is_some_and(|ret| ret.is_err()));
+
+        Ok(())
+    }
+}
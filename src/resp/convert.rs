@@ -0,0 +1,527 @@
+use std::{
+    collections::HashMap,
+    hash::{BuildHasher, Hash},
+    str::FromStr,
+};
+
+use bigdecimal::{BigDecimal, FromPrimitive, ToPrimitive};
+use bytes::Bytes;
+use thiserror::Error;
+
+use crate::resp::{BigNumber, BulkString, RespFrame, RespFrameArray, RespMap, SimpleNull};
+
+/// Converts a decoded frame into a Rust value.
+///
+/// `u8` is deliberately not implemented so that `Vec<u8>` can mean a byte string rather than
+/// an array of small integers.
+pub trait FromRespFrame: Sized {
+    fn from_resp_frame(frame: RespFrame) -> Result<Self, RespConvertError>;
+}
+
+/// Converts a Rust value into a frame, for building replies.
+pub trait IntoRespFrame {
+    fn into_resp_frame(self) -> RespFrame;
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum RespConvertError {
+    #[error("Expect {expected} but got {got}")]
+    UnexpectedFrame { expected: &'static str, got: String },
+    #[error("Invalid value for {expected}: {value}")]
+    InvalidValue {
+        expected: &'static str,
+        value: String,
+    },
+    #[error("Expect {expected} elements but got {got}")]
+    InvalidLength { expected: usize, got: usize },
+    #[error("Error reply: {0}")]
+    ErrorReply(String),
+}
+
+impl RespFrame {
+    /// Converts the frame into any type implementing [`FromRespFrame`].
+    pub fn into_value<T: FromRespFrame>(self) -> Result<T, RespConvertError> {
+        T::from_resp_frame(self)
+    }
+}
+
+// Error replies are surfaced as such rather than as a type mismatch, and attributes are
+// transparent to conversions.
+fn unexpected(expected: &'static str, frame: RespFrame) -> RespConvertError {
+    match frame {
+        RespFrame::Errors(e) => RespConvertError::ErrorReply(e.0),
+        RespFrame::BulkErrors(e) => {
+            RespConvertError::ErrorReply(String::from_utf8_lossy(&e).into_owned())
+        }
+        frame => RespConvertError::UnexpectedFrame {
+            expected,
+            got: format!("{:?}", frame),
+        },
+    }
+}
+
+fn strip_attribute(frame: RespFrame) -> RespFrame {
+    match frame {
+        RespFrame::Attribute(attribute) => attribute.into_frame(),
+        frame => frame,
+    }
+}
+
+fn invalid_value(expected: &'static str, value: impl ToString) -> RespConvertError {
+    RespConvertError::InvalidValue {
+        expected,
+        value: value.to_string(),
+    }
+}
+
+// the text of frames which carry a string, e.g. numbers sent as bulk strings by RESP2 servers
+fn into_text(frame: RespFrame) -> Result<Vec<u8>, RespFrame> {
+    match frame {
+        RespFrame::Strings(s) => Ok(s.0.into_bytes()),
+        RespFrame::BulkStrings(s) => Ok(s.to_vec()),
+        RespFrame::VerbatimStrings(s) => Ok(s.to_vec()),
+        frame => Err(frame),
+    }
+}
+
+fn parse_text<T: FromStr>(expected: &'static str, text: Vec<u8>) -> Result<T, RespConvertError> {
+    let text = String::from_utf8_lossy(&text);
+    text.parse().map_err(|_| invalid_value(expected, text))
+}
+
+fn into_elements(
+    expected: &'static str,
+    frame: RespFrame,
+) -> Result<Vec<RespFrame>, RespConvertError> {
+    match strip_attribute(frame) {
+        RespFrame::Array(frames) => Ok(frames.0),
+        RespFrame::Set(frames) => Ok(frames.into_iter().collect()),
+        RespFrame::Push(frames) => Ok(frames.0),
+        frame => Err(unexpected(expected, frame)),
+    }
+}
+
+impl FromRespFrame for RespFrame {
+    fn from_resp_frame(frame: RespFrame) -> Result<Self, RespConvertError> {
+        Ok(frame)
+    }
+}
+
+impl FromRespFrame for String {
+    fn from_resp_frame(frame: RespFrame) -> Result<Self, RespConvertError> {
+        match into_text(strip_attribute(frame)) {
+            Ok(text) => String::from_utf8(text)
+                .map_err(|e| invalid_value("utf-8 string", String::from_utf8_lossy(e.as_bytes()))),
+            Err(RespFrame::Integers(i)) => Ok(i.to_string()),
+            Err(RespFrame::Doubles(f)) => Ok(f.to_string()),
+            Err(RespFrame::BigNumbers(num)) => Ok(num.to_plain_string()),
+            Err(frame) => Err(unexpected("string", frame)),
+        }
+    }
+}
+
+impl FromRespFrame for Vec<u8> {
+    fn from_resp_frame(frame: RespFrame) -> Result<Self, RespConvertError> {
+        into_text(strip_attribute(frame)).map_err(|frame| unexpected("bytes", frame))
+    }
+}
+
+impl FromRespFrame for Bytes {
+    fn from_resp_frame(frame: RespFrame) -> Result<Self, RespConvertError> {
+        match strip_attribute(frame) {
+            RespFrame::BulkStrings(s) => Ok(s.0),
+            frame => Vec::<u8>::from_resp_frame(frame).map(Bytes::from),
+        }
+    }
+}
+
+macro_rules! impl_from_resp_frame_for_integers {
+    ($($t:ty)*) => {
+        $(
+            impl FromRespFrame for $t {
+                fn from_resp_frame(frame: RespFrame) -> Result<Self, RespConvertError> {
+                    let expected = stringify!($t);
+                    match into_text(strip_attribute(frame)) {
+                        Ok(text) => parse_text(expected, text),
+                        Err(RespFrame::Integers(i)) => {
+                            <$t>::try_from(i).map_err(|_| invalid_value(expected, i))
+                        }
+                        Err(RespFrame::BigNumbers(num)) if num.0.is_integer() => {
+                            parse_text(expected, num.to_plain_string().into_bytes())
+                        }
+                        Err(frame) => Err(unexpected(expected, frame)),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_from_resp_frame_for_integers!(i8 i16 i32 i64 i128 isize u16 u32 u64 u128 usize);
+
+impl FromRespFrame for f64 {
+    fn from_resp_frame(frame: RespFrame) -> Result<Self, RespConvertError> {
+        match into_text(strip_attribute(frame)) {
+            Ok(text) => parse_text("f64", text),
+            Err(RespFrame::Doubles(f)) => Ok(f),
+            Err(RespFrame::Integers(i)) => Ok(i as f64),
+            Err(RespFrame::BigNumbers(num)) => num
+                .0
+                .to_f64()
+                .ok_or_else(|| invalid_value("f64", num.to_plain_string())),
+            Err(frame) => Err(unexpected("f64", frame)),
+        }
+    }
+}
+
+impl FromRespFrame for bool {
+    fn from_resp_frame(frame: RespFrame) -> Result<Self, RespConvertError> {
+        match strip_attribute(frame) {
+            RespFrame::Booleans(b) => Ok(b),
+            // RESP2 replies booleans as integers
+            RespFrame::Integers(0) => Ok(false),
+            RespFrame::Integers(1) => Ok(true),
+            RespFrame::Integers(i) => Err(invalid_value("bool", i)),
+            frame => Err(unexpected("bool", frame)),
+        }
+    }
+}
+
+impl FromRespFrame for BigDecimal {
+    fn from_resp_frame(frame: RespFrame) -> Result<Self, RespConvertError> {
+        match into_text(strip_attribute(frame)) {
+            Ok(text) => parse_text("big number", text),
+            Err(RespFrame::BigNumbers(num)) => Ok(num.0),
+            Err(RespFrame::Integers(i)) => Ok(BigDecimal::from(i)),
+            Err(RespFrame::Doubles(f)) => {
+                BigDecimal::from_f64(f).ok_or_else(|| invalid_value("big number", f))
+            }
+            Err(frame) => Err(unexpected("big number", frame)),
+        }
+    }
+}
+
+impl<T: FromRespFrame> FromRespFrame for Option<T> {
+    fn from_resp_frame(frame: RespFrame) -> Result<Self, RespConvertError> {
+        match strip_attribute(frame) {
+            RespFrame::Nulls(_) | RespFrame::BulkNullStrings(_) | RespFrame::NullArray(_) => {
+                Ok(None)
+            }
+            frame => T::from_resp_frame(frame).map(Some),
+        }
+    }
+}
+
+impl<T: FromRespFrame> FromRespFrame for Vec<T> {
+    fn from_resp_frame(frame: RespFrame) -> Result<Self, RespConvertError> {
+        into_elements("array", frame)?
+            .into_iter()
+            .map(T::from_resp_frame)
+            .collect()
+    }
+}
+
+impl<K, V, S> FromRespFrame for HashMap<K, V, S>
+where
+    K: FromRespFrame + Eq + Hash,
+    V: FromRespFrame,
+    S: BuildHasher + Default,
+{
+    fn from_resp_frame(frame: RespFrame) -> Result<Self, RespConvertError> {
+        let pairs = match strip_attribute(frame) {
+            RespFrame::Map(pairs) => pairs.0,
+            // RESP2 replies maps as flat arrays of keys and values, e.g. HGETALL
+            RespFrame::Array(frames) if frames.len() % 2 == 0 => {
+                let mut pairs = Vec::with_capacity(frames.len() / 2);
+                let mut iter = frames.0.into_iter();
+                while let (Some(key), Some(value)) = (iter.next(), iter.next()) {
+                    pairs.push((key, value));
+                }
+                pairs
+            }
+            frame => return Err(unexpected("map", frame)),
+        };
+        pairs
+            .into_iter()
+            .map(|(key, value)| Ok((K::from_resp_frame(key)?, V::from_resp_frame(value)?)))
+            .collect()
+    }
+}
+
+macro_rules! impl_tuples {
+    ($($len:expr => ($($name:ident)+))+) => {
+        $(
+            impl<$($name: FromRespFrame),+> FromRespFrame for ($($name,)+) {
+                fn from_resp_frame(frame: RespFrame) -> Result<Self, RespConvertError> {
+                    let frames = into_elements("array", frame)?;
+                    if frames.len() != $len {
+                        return Err(RespConvertError::InvalidLength {
+                            expected: $len,
+                            got: frames.len(),
+                        });
+                    }
+                    let mut iter = frames.into_iter();
+                    Ok(($($name::from_resp_frame(iter.next().expect("length is checked"))?,)+))
+                }
+            }
+
+            impl<$($name: IntoRespFrame),+> IntoRespFrame for ($($name,)+) {
+                #[allow(non_snake_case)]
+                fn into_resp_frame(self) -> RespFrame {
+                    let ($($name,)+) = self;
+                    RespFrameArray::new(vec![$($name.into_resp_frame()),+]).into()
+                }
+            }
+        )+
+    };
+}
+
+impl_tuples! {
+    1 => (A)
+    2 => (A B)
+    3 => (A B C)
+    4 => (A B C D)
+    5 => (A B C D E)
+    6 => (A B C D E F)
+}
+
+impl IntoRespFrame for RespFrame {
+    fn into_resp_frame(self) -> RespFrame {
+        self
+    }
+}
+
+impl IntoRespFrame for String {
+    fn into_resp_frame(self) -> RespFrame {
+        BulkString::new(self).into()
+    }
+}
+
+impl IntoRespFrame for &str {
+    fn into_resp_frame(self) -> RespFrame {
+        BulkString::new(self).into()
+    }
+}
+
+impl IntoRespFrame for Vec<u8> {
+    fn into_resp_frame(self) -> RespFrame {
+        BulkString::new(self).into()
+    }
+}
+
+impl IntoRespFrame for &[u8] {
+    fn into_resp_frame(self) -> RespFrame {
+        BulkString::new(self).into()
+    }
+}
+
+impl IntoRespFrame for Bytes {
+    fn into_resp_frame(self) -> RespFrame {
+        BulkString::from(self).into()
+    }
+}
+
+macro_rules! impl_into_resp_frame_for_integers {
+    ($($t:ty)*) => {
+        $(
+            impl IntoRespFrame for $t {
+                fn into_resp_frame(self) -> RespFrame {
+                    match i64::try_from(self) {
+                        Ok(i) => i.into(),
+                        Err(_) => BigNumber::new(&self.to_string())
+                            .expect("integers are valid big numbers")
+                            .into(),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_into_resp_frame_for_integers!(i8 i16 i32 i64 i128 isize u16 u32 u64 u128 usize);
+
+impl IntoRespFrame for f64 {
+    fn into_resp_frame(self) -> RespFrame {
+        self.into()
+    }
+}
+
+impl IntoRespFrame for bool {
+    fn into_resp_frame(self) -> RespFrame {
+        self.into()
+    }
+}
+
+impl IntoRespFrame for BigDecimal {
+    fn into_resp_frame(self) -> RespFrame {
+        BigNumber(self).into()
+    }
+}
+
+impl<T: IntoRespFrame> IntoRespFrame for Option<T> {
+    fn into_resp_frame(self) -> RespFrame {
+        match self {
+            Some(value) => value.into_resp_frame(),
+            None => SimpleNull.into(),
+        }
+    }
+}
+
+impl<T: IntoRespFrame> IntoRespFrame for Vec<T> {
+    fn into_resp_frame(self) -> RespFrame {
+        let frames: Vec<RespFrame> = self.into_iter().map(T::into_resp_frame).collect();
+        RespFrameArray::new(frames).into()
+    }
+}
+
+impl<K: IntoRespFrame, V: IntoRespFrame, S> IntoRespFrame for HashMap<K, V, S> {
+    fn into_resp_frame(self) -> RespFrame {
+        let pairs: Vec<(RespFrame, RespFrame)> = self
+            .into_iter()
+            .map(|(key, value)| (key.into_resp_frame(), value.into_resp_frame()))
+            .collect();
+        RespMap::new(pairs).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, str::FromStr};
+
+    use bigdecimal::BigDecimal;
+
+    use crate::resp::*;
+    use anyhow::Result;
+
+    #[test]
+    fn test_scalars_from_frame() -> Result<()> {
+        assert_eq!(String::from_resp_frame(b"hello".into())?, "hello");
+        assert_eq!(
+            String::from_resp_frame(SimpleString::new("OK").into())?,
+            "OK"
+        );
+        assert_eq!(String::from_resp_frame(42.into())?, "42");
+        assert_eq!(Vec::<u8>::from_resp_frame(b"\xff\x00".into())?, b"\xff\x00");
+
+        assert_eq!(i64::from_resp_frame((-5).into())?, -5);
+        assert_eq!(u16::from_resp_frame(b"8080".into())?, 8080);
+        assert_eq!(
+            u128::from_resp_frame(
+                BigNumber::new("340282366920938463463374607431768211455")?.into()
+            )?,
+            u128::MAX
+        );
+        assert_eq!(f64::from_resp_frame(b"1.5".into())?, 1.5);
+        assert_eq!(f64::from_resp_frame(2.into())?, 2.0);
+        assert!(bool::from_resp_frame(true.into())?);
+        assert!(!bool::from_resp_frame(0.into())?);
+        assert_eq!(
+            BigDecimal::from_resp_frame(BigNumber::new("-12.5")?.into())?,
+            BigDecimal::from_str("-12.5")?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_containers_from_frame() -> Result<()> {
+        let frame: RespFrame =
+            RespFrameArray::new(vec![b"a".into(), BulkNullString.into(), b"c".into()]).into();
+        let values: Vec<Option<String>> = frame.into_value()?;
+        assert_eq!(
+            values,
+            vec![Some("a".to_string()), None, Some("c".to_string())]
+        );
+
+        let frame: RespFrame =
+            RespMap::new(vec![(b"x".into(), 1.into()), (b"y".into(), b"2".into())]).into();
+        let map: HashMap<String, i64> = frame.into_value()?;
+        assert_eq!(
+            map,
+            HashMap::from([("x".to_string(), 1), ("y".to_string(), 2)])
+        );
+
+        // HGETALL style flat array
+        let frame: RespFrame =
+            RespFrameArray::new(vec![b"x".into(), b"1".into(), b"y".into(), b"2".into()]).into();
+        let map: HashMap<String, u32> = frame.into_value()?;
+        assert_eq!(
+            map,
+            HashMap::from([("x".to_string(), 1), ("y".to_string(), 2)])
+        );
+
+        let frame: RespFrame = RespFrameArray::new(vec![b"k".into(), 10.into(), 1.5.into()]).into();
+        let tuple: (String, i32, f64) = frame.into_value()?;
+        assert_eq!(tuple, ("k".to_string(), 10, 1.5));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_conversion_errors() {
+        assert_eq!(
+            i8::from_resp_frame(300.into()).unwrap_err(),
+            RespConvertError::InvalidValue {
+                expected: "i8",
+                value: "300".to_string()
+            }
+        );
+        assert_eq!(
+            i64::from_resp_frame(b"abc".into()).unwrap_err(),
+            RespConvertError::InvalidValue {
+                expected: "i64",
+                value: "abc".to_string()
+            }
+        );
+        assert_eq!(
+            String::from_resp_frame(SimpleError::new("ERR no such key").into()).unwrap_err(),
+            RespConvertError::ErrorReply("ERR no such key".to_string())
+        );
+        assert!(matches!(
+            bool::from_resp_frame(b"yes".into()).unwrap_err(),
+            RespConvertError::UnexpectedFrame {
+                expected: "bool",
+                ..
+            }
+        ));
+        let frame: RespFrame = RespFrameArray::new(vec![1.into()]).into();
+        assert_eq!(
+            <(i64, i64)>::from_resp_frame(frame).unwrap_err(),
+            RespConvertError::InvalidLength {
+                expected: 2,
+                got: 1
+            }
+        );
+        let frame: RespFrame = RespFrameArray::new(vec![1.into(), b"x".into()]).into();
+        assert!(Vec::<i64>::from_resp_frame(frame).is_err());
+    }
+
+    #[test]
+    fn test_into_frame() -> Result<()> {
+        assert_eq!("hello".into_resp_frame(), b"hello".into());
+        assert_eq!(42u32.into_resp_frame(), 42.into());
+        assert_eq!(
+            u64::MAX.into_resp_frame(),
+            BigNumber::new("18446744073709551615")?.into()
+        );
+        assert_eq!(None::<String>.into_resp_frame(), SimpleNull.into());
+        assert_eq!(
+            vec![("a", 1), ("b", 2)].into_resp_frame(),
+            RespFrameArray::new(vec![
+                RespFrameArray::new(vec![b"a".into(), 1.into()]).into(),
+                RespFrameArray::new(vec![b"b".into(), 2.into()]).into(),
+            ])
+            .into()
+        );
+        assert_eq!(
+            HashMap::from([("k", true)]).into_resp_frame(),
+            RespMap::new(vec![(b"k".into(), true.into())]).into()
+        );
+
+        let frame = (String::from("v"), Some(1.5), vec![1i64, 2]).into_resp_frame();
+        let value: (String, Option<f64>, Vec<i64>) = frame.into_value()?;
+        assert_eq!(value, ("v".to_string(), Some(1.5), vec![1, 2]));
+
+        Ok(())
+    }
+}
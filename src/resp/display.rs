@@ -0,0 +1,201 @@
+use std::fmt::{self, Write};
+
+use crate::resp::{simple::double_text, RespFrame};
+
+/// Renders the frame the way `redis-cli` prints replies on a terminal, e.g.
+///
+/// ```text
+/// 1) "set"
+/// 2) 1) (integer) 5
+///    2) (nil)
+/// ```
+impl fmt::Display for RespFrame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut out = String::new();
+        render(self, "", &mut out);
+        f.write_str(out.strip_suffix('\n').unwrap_or(&out))
+    }
+}
+
+// every rendered frame ends with a newline, nested lines are indented by `prefix`
+fn render(frame: &RespFrame, prefix: &str, out: &mut String) {
+    match frame {
+        RespFrame::Strings(s) => out.push_str(s),
+        RespFrame::Errors(e) => {
+            out.push_str("(error) ");
+            out.push_str(e);
+        }
+        RespFrame::Integers(i) => {
+            let _ = write!(out, "(integer) {}", i);
+        }
+        RespFrame::Nulls(_) | RespFrame::BulkNullStrings(_) | RespFrame::NullArray(_) => {
+            out.push_str("(nil)")
+        }
+        RespFrame::Booleans(b) => {
+            let _ = write!(out, "({})", b);
+        }
+        RespFrame::Doubles(f) => {
+            out.push_str("(double) ");
+            out.push_str(&double_text(*f));
+        }
+        RespFrame::BigNumbers(num) => {
+            let _ = write!(out, "(big number) {}", num.to_plain_string());
+        }
+        RespFrame::BulkStrings(s) => push_quoted(s, out),
+        RespFrame::BulkErrors(e) => {
+            out.push_str("(error) ");
+            out.push_str(&String::from_utf8_lossy(e));
+        }
+        RespFrame::VerbatimStrings(s) => out.push_str(&String::from_utf8_lossy(s)),
+        RespFrame::Array(frames) => {
+            let entries = frames.iter().map(|frame| (frame, None));
+            return render_aggregate("array", ')', entries, prefix, out);
+        }
+        RespFrame::Set(frames) => {
+            let entries = frames.iter().map(|frame| (frame, None));
+            return render_aggregate("set", '~', entries, prefix, out);
+        }
+        RespFrame::Push(frames) => {
+            let entries = frames.iter().map(|frame| (frame, None));
+            return render_aggregate("push", ')', entries, prefix, out);
+        }
+        RespFrame::Map(pairs) => {
+            let entries = pairs.iter().map(|(key, value)| (key, Some(value)));
+            return render_aggregate("hash", '#', entries, prefix, out);
+        }
+        // attributes are out-of-band metadata, redis-cli doesn't show them either
+        RespFrame::Attribute(attribute) => return render(&attribute.frame, prefix, out),
+    }
+    out.push('\n');
+}
+
+fn render_aggregate<'a>(
+    name: &str,
+    separator: char,
+    entries: impl ExactSizeIterator<Item = (&'a RespFrame, Option<&'a RespFrame>)>,
+    prefix: &str,
+    out: &mut String,
+) {
+    if entries.len() == 0 {
+        let _ = writeln!(out, "(empty {})", name);
+        return;
+    }
+
+    // nested lines are aligned after the widest index
+    let width = entries.len().to_string().len();
+    let nested_prefix = format!("{}{}", prefix, " ".repeat(width + 2));
+    for (i, (frame, value)) in entries.enumerate() {
+        // the first entry goes on the line the parent already started
+        let _ = write!(
+            out,
+            "{}{:>width$}{} ",
+            if i == 0 { "" } else { prefix },
+            i + 1,
+            separator,
+            width = width
+        );
+        render(frame, &nested_prefix, out);
+        if let Some(value) = value {
+            out.pop();
+            out.push_str(" => ");
+            render(value, &nested_prefix, out);
+        }
+    }
+}
+
+// quotes and escapes like `sdscatrepr`
+fn push_quoted(data: &[u8], out: &mut String) {
+    out.push('"');
+    for &b in data {
+        match b {
+            b'\\' => out.push_str("\\\\"),
+            b'"' => out.push_str("\\\""),
+            b'\n' => out.push_str("\\n"),
+            b'\r' => out.push_str("\\r"),
+            b'\t' => out.push_str("\\t"),
+            0x07 => out.push_str("\\a"),
+            0x08 => out.push_str("\\b"),
+            b' '..=b'~' => out.push(b as char),
+            _ => {
+                let _ = write!(out, "\\x{:02x}", b);
+            }
+        }
+    }
+    out.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::resp::*;
+    use anyhow::Result;
+
+    #[test]
+    fn test_display_scalars() -> Result<()> {
+        assert_eq!(RespFrame::from(SimpleString::new("OK")).to_string(), "OK");
+        assert_eq!(
+            RespFrame::from(SimpleError::new("ERR unknown command")).to_string(),
+            "(error) ERR unknown command"
+        );
+        assert_eq!(RespFrame::from(5).to_string(), "(integer) 5");
+        assert_eq!(RespFrame::from(SimpleNull).to_string(), "(nil)");
+        assert_eq!(RespFrame::from(BulkNullString).to_string(), "(nil)");
+        assert_eq!(RespFrame::from(true).to_string(), "(true)");
+        assert_eq!(RespFrame::from(1.5).to_string(), "(double) 1.5");
+        assert_eq!(
+            RespFrame::from(BigNumber::new("12345678901234567890")?).to_string(),
+            "(big number) 12345678901234567890"
+        );
+        assert_eq!(RespFrame::from(b"set").to_string(), "\"set\"");
+        assert_eq!(
+            RespFrame::from(b"a \"b\"\r\n\x00\xff").to_string(),
+            r#""a \"b\"\r\n\x00\xff""#
+        );
+        assert_eq!(
+            RespFrame::from(VerbatimString::txt("Some string")).to_string(),
+            "Some string"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_display_aggregates() {
+        let frame: RespFrame = RespFrameArray::new(vec![
+            b"set".into(),
+            RespFrameArray::new(vec![5.into(), BulkNullString.into()]).into(),
+            RespFrameArray::new(vec![]).into(),
+        ])
+        .into();
+        assert_eq!(
+            frame.to_string(),
+            "1) \"set\"\n2) 1) (integer) 5\n   2) (nil)\n3) (empty array)"
+        );
+
+        let frames = (1..=10).map(RespFrame::from).collect::<Vec<_>>();
+        let frame: RespFrame = RespFrameArray::new(frames).into();
+        assert!(frame
+            .to_string()
+            .starts_with(" 1) (integer) 1\n 2) (integer) 2\n"));
+        assert!(frame.to_string().ends_with("\n10) (integer) 10"));
+
+        let frame: RespFrame = RespMap::new(vec![
+            (b"server".into(), b"redis".into()),
+            (
+                b"modules".into(),
+                RespFrameArray::new(vec![b"a".into(), b"b".into()]).into(),
+            ),
+        ])
+        .into();
+        assert_eq!(
+            frame.to_string(),
+            "1# \"server\" => \"redis\"\n2# \"modules\" => 1) \"a\"\n   2) \"b\""
+        );
+
+        let frame: RespFrame = RespSet::new(vec![b"x".into()]).into();
+        assert_eq!(frame.to_string(), "1~ \"x\"");
+        assert_eq!(
+            RespFrame::from(RespMap::new(vec![])).to_string(),
+            "(empty hash)"
+        );
+    }
+}
@@ -1,9 +1,13 @@
 use enum_dispatch::enum_dispatch;
 
 use super::{
-    aggregate::{BulkNullString, BulkString, RespFrameArray, RespNullArray},
+    aggregate::{
+        BulkError, BulkNullString, BulkString, RespAttribute, RespFrameArray, RespMap,
+        RespNullArray, RespPush, RespSet, VerbatimString,
+    },
+    parser::{decode_frame, frame_len},
     simple::{BigNumber, SimpleError, SimpleNull, SimpleString},
-    RespDecode, RespError,
+    RespDecode, RespError, RespLimits,
 };
 
 #[enum_dispatch(RespEncode)]
@@ -18,62 +22,42 @@ pub enum RespFrame {
     BigNumbers(BigNumber),
     BulkStrings(BulkString),
     BulkNullStrings(BulkNullString),
+    BulkErrors(BulkError),
+    VerbatimStrings(VerbatimString),
     NullArray(RespNullArray),
     Array(RespFrameArray),
+    Map(RespMap),
+    Set(RespSet),
+    Push(RespPush),
+    Attribute(RespAttribute),
 }
 
 impl RespDecode for RespFrame {
     const PREFIX: &'static str = "";
     fn decode(buf: &mut bytes::BytesMut) -> Result<Self, RespError> {
-        let mut iter = buf.iter().peekable();
-        match iter.peek() {
-            Some(b'+') => SimpleString::decode(buf).map(RespFrame::Strings),
-            Some(b'-') => SimpleError::decode(buf).map(RespFrame::Errors),
-            Some(b':') => i64::decode(buf).map(RespFrame::Integers),
-            Some(b'$') => match BulkNullString::decode(buf) {
-                Ok(frame) => Ok(RespFrame::BulkNullStrings(frame)),
-                Err(RespError::NotComplete) => Err(RespError::NotComplete),
-                Err(_) => {
-                    let frame = BulkString::decode(buf)?;
-                    Ok(frame.into())
-                }
-            },
-            Some(b'*') => match RespNullArray::decode(buf) {
-                Ok(frame) => Ok(RespFrame::NullArray(frame)),
-                Err(RespError::NotComplete) => Err(RespError::NotComplete),
-                Err(_) => {
-                    let frame = RespFrameArray::decode(buf)?;
-                    Ok(frame.into())
-                }
-            },
-            Some(b'_') => SimpleNull::decode(buf).map(RespFrame::Nulls),
-            Some(b'#') => bool::decode(buf).map(RespFrame::Booleans),
-            Some(b',') => f64::decode(buf).map(RespFrame::Doubles),
-            None => Err(RespError::NotComplete),
-            _ => Err(RespError::InvalidFrameType(format!(
-                "expect_length: unknown frame type: {:?}",
-                buf
-            ))),
-        }
+        decode_frame(buf, Self::PREFIX, &RespLimits::default())
     }
     fn expect_length(buf: &[u8]) -> Result<usize, RespError> {
-        let mut iter = buf.iter().peekable();
-        match iter.peek() {
-            Some(b'+') => SimpleString::expect_length(buf),
-            Some(b'-') => SimpleError::expect_length(buf),
-            Some(b':') => i64::expect_length(buf),
-            Some(b'$') => BulkString::expect_length(buf),
-            Some(b'*') => RespFrameArray::expect_length(buf),
-            Some(b'_') => SimpleNull::expect_length(buf),
-            Some(b'#') => bool::expect_length(buf),
-            Some(b',') => f64::expect_length(buf),
-            _ => Err(RespError::NotComplete),
-        }
+        frame_len(buf, Self::PREFIX, &RespLimits::default())
+    }
+}
+
+impl RespFrame {
+    /// Like [`RespFrame::decode`], but with custom decoder limits instead of the defaults.
+    ///
+    /// Bulk string payloads are split off `buf` without copying. The buffer is left as it is
+    /// until a whole frame has arrived, so prefer [`RespParser`](crate::RespParser) when
+    /// reading large frames in many pieces.
+    pub fn decode_with_limits(
+        buf: &mut bytes::BytesMut,
+        limits: &RespLimits,
+    ) -> Result<Self, RespError> {
+        decode_frame(buf, Self::PREFIX, limits)
     }
 }
 
 impl<const N: usize> From<&[u8; N]> for RespFrame {
     fn from(buf: &[u8; N]) -> Self {
-        BulkString(buf.to_vec()).into()
+        BulkString::new(buf.to_vec()).into()
     }
 }
@@ -0,0 +1,225 @@
+use bytes::BytesMut;
+
+use crate::resp::{BulkString, RespError, RespFrameArray};
+
+/// Decodes an inline command such as `SET foo "bar baz"\r\n`, the way redis accepts
+/// commands typed into telnet or nc, into an array of bulk strings. Blank lines are skipped.
+pub(crate) fn decode_inline(
+    buf: &mut BytesMut,
+    max_inline_len: usize,
+) -> Result<RespFrameArray, RespError> {
+    loop {
+        let Some(end) = buf.iter().position(|&b| b == b'\n') else {
+            if buf.len() > max_inline_len {
+                return Err(RespError::LineTooLong(max_inline_len));
+            }
+            return Err(RespError::NotComplete);
+        };
+        if end > max_inline_len {
+            return Err(RespError::LineTooLong(max_inline_len));
+        }
+
+        let line = buf.split_to(end + 1);
+        let line = line[..end].strip_suffix(b"\r").unwrap_or(&line[..end]);
+        let args = split_args(line)?;
+        if !args.is_empty() {
+            let frames = args.into_iter().map(|arg| BulkString::new(arg).into());
+            return Ok(RespFrameArray::new(frames.collect::<Vec<_>>()));
+        }
+        if buf.is_empty() {
+            return Err(RespError::NotComplete);
+        }
+    }
+}
+
+/// Returns true if `buf` holds an inline command rather than a RESP request, which is
+/// always an array.
+pub(crate) fn is_inline(buf: &[u8]) -> bool {
+    matches!(buf.first(), Some(b) if *b != b'*')
+}
+
+fn is_space(b: u8) -> bool {
+    matches!(b, b' ' | b'\t' | b'\n' | b'\r' | 0x0b | 0x0c)
+}
+
+fn hex_digit(b: u8) -> Option<u8> {
+    (b as char).to_digit(16).map(|d| d as u8)
+}
+
+fn unbalanced_quotes() -> RespError {
+    RespError::InvalidFrame("unbalanced quotes in inline command".to_string())
+}
+
+// Splits a line into arguments like redis' `sdssplitargs`: arguments are separated by
+// spaces, "double quotes" support `\n`, `\r`, `\t`, `\b`, `\a` and `\xHH` escapes, and
+// 'single quotes' only support `\'`. A closing quote must be followed by a space.
+fn split_args(line: &[u8]) -> Result<Vec<Vec<u8>>, RespError> {
+    let mut args = Vec::new();
+    let mut i = 0;
+    loop {
+        while i < line.len() && is_space(line[i]) {
+            i += 1;
+        }
+        if i == line.len() {
+            return Ok(args);
+        }
+
+        let mut arg = Vec::new();
+        let mut in_double_quotes = false;
+        let mut in_single_quotes = false;
+        loop {
+            let c = line.get(i).copied();
+            if in_double_quotes {
+                match c {
+                    None => return Err(unbalanced_quotes()),
+                    Some(b'\\') if line.get(i + 1) == Some(&b'x') => {
+                        let hex = line
+                            .get(i + 2)
+                            .and_then(|&h| hex_digit(h))
+                            .zip(line.get(i + 3).and_then(|&l| hex_digit(l)));
+                        match hex {
+                            Some((h, l)) => {
+                                arg.push(h * 16 + l);
+                                i += 3;
+                            }
+                            None => {
+                                arg.push(b'x');
+                                i += 1;
+                            }
+                        }
+                    }
+                    Some(b'\\') if i + 1 < line.len() => {
+                        let escaped = match line[i + 1] {
+                            b'n' => b'\n',
+                            b'r' => b'\r',
+                            b't' => b'\t',
+                            b'b' => 0x08,
+                            b'a' => 0x07,
+                            other => other,
+                        };
+                        arg.push(escaped);
+                        i += 1;
+                    }
+                    Some(b'"') => {
+                        if line.get(i + 1).is_some_and(|&b| !is_space(b)) {
+                            return Err(unbalanced_quotes());
+                        }
+                        i += 1;
+                        break;
+                    }
+                    Some(c) => arg.push(c),
+                }
+            } else if in_single_quotes {
+                match c {
+                    None => return Err(unbalanced_quotes()),
+                    Some(b'\\') if line.get(i + 1) == Some(&b'\'') => {
+                        arg.push(b'\'');
+                        i += 1;
+                    }
+                    Some(b'\'') => {
+                        if line.get(i + 1).is_some_and(|&b| !is_space(b)) {
+                            return Err(unbalanced_quotes());
+                        }
+                        i += 1;
+                        break;
+                    }
+                    Some(c) => arg.push(c),
+                }
+            } else {
+                match c {
+                    None => break,
+                    Some(c) if is_space(c) => break,
+                    Some(b'"') => in_double_quotes = true,
+                    Some(b'\'') => in_single_quotes = true,
+                    Some(c) => arg.push(c),
+                }
+            }
+            i += 1;
+        }
+        args.push(arg);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resp::RespParser;
+    use crate::RespFrame;
+    use anyhow::Result;
+
+    fn command(args: &[&[u8]]) -> RespFrameArray {
+        RespFrameArray::new(
+            args.iter()
+                .map(|arg| BulkString::new(*arg).into())
+                .collect::<Vec<RespFrame>>(),
+        )
+    }
+
+    #[test]
+    fn test_split_args() -> Result<()> {
+        assert_eq!(split_args(b"PING")?, vec![b"PING".to_vec()]);
+        assert_eq!(
+            split_args(b"  SET foo   \"bar baz\" ")?,
+            vec![b"SET".to_vec(), b"foo".to_vec(), b"bar baz".to_vec()]
+        );
+        assert_eq!(
+            split_args(br#"SET "a\"b\n\x41\xzz" 'it\'s "x"' """#)?,
+            vec![
+                b"SET".to_vec(),
+                b"a\"b\nAxzz".to_vec(),
+                b"it's \"x\"".to_vec(),
+                b"".to_vec()
+            ]
+        );
+        assert!(split_args(b"").is_ok_and(|args| args.is_empty()));
+
+        for line in [&b"SET \"foo"[..], b"SET 'foo", b"SET \"foo\"bar"] {
+            assert!(matches!(
+                split_args(line).unwrap_err(),
+                RespError::InvalidFrame(_)
+            ));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_inline() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"PING\r\n\r\nSET foo \"bar baz\"\nGET");
+
+        let frame = decode_inline(&mut buf, 1024)?;
+        assert_eq!(frame, command(&[b"PING"]));
+
+        let frame = decode_inline(&mut buf, 1024)?;
+        assert_eq!(frame, command(&[b"SET", b"foo", b"bar baz"]));
+
+        let ret = decode_inline(&mut buf, 1024);
+        assert_eq!(ret.unwrap_err(), RespError::NotComplete);
+
+        buf.extend_from_slice(b" foo\r\n");
+        let frame = decode_inline(&mut buf, 1024)?;
+        assert_eq!(frame, command(&[b"GET", b"foo"]));
+        assert!(buf.is_empty());
+
+        buf.extend_from_slice(b"GET aaaaaaaaaa");
+        let ret = decode_inline(&mut buf, 8);
+        assert_eq!(ret.unwrap_err(), RespError::LineTooLong(8));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_request_mixes_inline_and_resp() -> Result<()> {
+        let mut parser = RespParser::new();
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*1\r\n$4\r\nPING\r\nECHO 'hi there'\r\n");
+
+        let frame = parser.parse_request(&mut buf)?;
+        assert_eq!(frame, command(&[b"PING"]).into());
+
+        let frame = parser.parse_request(&mut buf)?;
+        assert_eq!(frame, command(&[b"ECHO", b"hi there"]).into());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,35 @@
+/// Upper bounds the decoder enforces on untrusted input, so a peer cannot make the
+/// server allocate or buffer arbitrary amounts of memory with a few header bytes, and how
+/// strictly it validates what it reads.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RespLimits {
+    /// Longest payload of a bulk string, bulk error or verbatim string.
+    pub max_bulk_len: usize,
+    /// Most elements of an array, set or push, or pairs of a map or attribute.
+    pub max_aggregate_len: usize,
+    /// Deepest nesting of aggregate frames.
+    pub max_depth: usize,
+    /// Longest line, e.g. a simple string or a length header, without its CRLF.
+    pub max_inline_len: usize,
+    /// Most characters a big number may have when written out. An exponent is expanded to
+    /// digits when the number is encoded, so `(1e9000000000` would otherwise take gigabytes.
+    pub max_big_number_len: usize,
+    /// Reject simple strings and errors that aren't valid utf-8 instead of replacing the
+    /// invalid bytes, use [`RawSimpleString`](crate::RawSimpleString) to keep them as sent.
+    pub strict_utf8: bool,
+}
+
+impl Default for RespLimits {
+    // the same defaults redis uses, `proto-max-bulk-len` and `PROTO_INLINE_MAX_SIZE`; a big
+    // number at the limit still fits on a line together with its `(` and sign
+    fn default() -> Self {
+        RespLimits {
+            max_bulk_len: 512 * 1024 * 1024,
+            max_aggregate_len: i32::MAX as usize,
+            max_depth: 128,
+            max_inline_len: 64 * 1024,
+            max_big_number_len: 64 * 1024 - 2,
+            strict_utf8: false,
+        }
+    }
+}
@@ -1,17 +1,39 @@
-use aggregate::{BulkNullString, BulkString, RespFrameArray, RespNullArray};
-use bytes::{Buf, BytesMut};
+pub use aggregate::{
+    BulkError, BulkNullString, BulkString, RespAttribute, RespFrameArray, RespMap, RespNullArray,
+    RespPush, RespSet, VerbatimString,
+};
+use bytes::{Buf, BufMut, BytesMut};
+pub use codec::{RespCodec, RespCodecError};
+pub use convert::{FromRespFrame, IntoRespFrame, RespConvertError};
 use enum_dispatch::enum_dispatch;
 pub use frame::RespFrame;
-use simple::{BigNumber, SimpleError, SimpleNull, SimpleString};
+pub use limits::RespLimits;
+pub use parser::RespParser;
+#[cfg(feature = "serde")]
+pub use serde::{from_frame, to_frame, RespSerdeError};
+pub use simple::{
+    BigNumber, RawSimpleError, RawSimpleString, SimpleError, SimpleNull, SimpleString,
+};
 use thiserror::Error;
+pub use version::RespVersion;
 
 mod aggregate;
+#[cfg(test)]
+mod arbitrary;
+mod codec;
+mod convert;
+mod display;
 mod frame;
+mod inline;
+mod limits;
+mod parser;
+#[cfg(feature = "serde")]
+mod serde;
 mod simple;
+mod version;
 
 const CRLF: &[u8] = b"\r\n";
 const CRLF_LEN: usize = CRLF.len();
-const BUF_CAP: usize = 4096;
 
 pub trait RespDecode: Sized {
     const PREFIX: &'static str;
@@ -23,7 +45,20 @@ pub trait RespDecode: Sized {
 
 #[enum_dispatch]
 pub trait RespEncode {
-    fn encode(self) -> Vec<u8>;
+    /// Writes the encoded frame into `buf`.
+    fn encode_to<B: BufMut>(&self, buf: &mut B);
+
+    /// The exact number of bytes `encode_to` writes.
+    fn encoded_len(&self) -> usize;
+
+    fn encode(self) -> Vec<u8>
+    where
+        Self: Sized,
+    {
+        let mut buf = Vec::with_capacity(self.encoded_len());
+        self.encode_to(&mut buf);
+        buf
+    }
 }
 
 #[derive(Error, Debug, PartialEq)]
@@ -36,6 +71,16 @@ pub enum RespError {
     InvalidFrameLength(isize),
     #[error("Frame is not complete")]
     NotComplete,
+    #[error("Bulk length {0} exceeds the limit")]
+    BulkTooLong(usize),
+    #[error("Aggregate length {0} exceeds the limit")]
+    AggregateTooLong(usize),
+    #[error("Nesting depth exceeds the limit of {0}")]
+    NestingTooDeep(usize),
+    #[error("Line exceeds the limit of {0} bytes")]
+    LineTooLong(usize),
+    #[error("Big number of {0} characters exceeds the limit")]
+    BigNumberTooLong(usize),
 
     #[error("Parse error: {0}")]
     ParseIntError(#[from] std::num::ParseIntError),
@@ -89,33 +134,69 @@ pub fn parse_length(buf: &[u8], prefix: &str) -> Result<(usize, usize), RespErro
     Ok((end, s.parse()?))
 }
 
-fn calc_total_length(buf: &[u8], end: usize, len: usize, prefix: &str) -> Result<usize, RespError> {
-    let mut total = end + CRLF_LEN;
-    let mut data = &buf[total..];
-    match prefix {
-        "*" | "~" => {
-            // find nth CRLF in the buffer, for array and set, we need to find 1 CRLF for each element
-            for _ in 0..len {
-                let len = RespFrame::expect_length(data)?;
-                data = &data[len..];
-                total += len;
-            }
-            Ok(total)
-        }
-        "%" => {
-            // find nth CRLF in the buffer. For map, we need to find 2 CRLF for each key-value pair
-            for _ in 0..len {
-                let len = SimpleString::expect_length(data)?;
-
-                data = &data[len..];
-                total += len;
-
-                let len = RespFrame::expect_length(data)?;
-                data = &data[len..];
-                total += len;
-            }
-            Ok(total)
+fn decimal_len(mut n: u64) -> usize {
+    let mut len = 1;
+    while n >= 10 {
+        n /= 10;
+        len += 1;
+    }
+    len
+}
+
+// writes the decimal digits of `n` without going through a formatter
+fn put_decimal<B: BufMut>(buf: &mut B, mut n: u64) {
+    let mut digits = [0u8; 20];
+    let mut start = digits.len();
+    loop {
+        start -= 1;
+        digits[start] = b'0' + (n % 10) as u8;
+        n /= 10;
+        if n == 0 {
+            break;
         }
-        _ => Ok(len + CRLF_LEN),
+    }
+    buf.put_slice(&digits[start..]);
+}
+
+// writes a `<prefix><len>\r\n` header of a bulk or aggregate frame
+fn put_length_header<B: BufMut>(buf: &mut B, prefix: u8, len: usize) {
+    buf.put_u8(prefix);
+    put_decimal(buf, len as u64);
+    buf.put_slice(CRLF);
+}
+
+fn length_header_len(len: usize) -> usize {
+    1 + decimal_len(len as u64) + CRLF_LEN
+}
+
+// The payload of a bulk frame must be followed by CRLF, anything else there means its
+// length header is wrong and the rest of the stream can't be trusted.
+fn check_bulk_end(remained: &[u8], len: usize) -> Result<(), RespError> {
+    if &remained[len..len + CRLF_LEN] != CRLF {
+        return Err(RespError::InvalidFrame(format!(
+            "Expect CRLF after a payload of {} bytes, got {:?}",
+            len,
+            String::from_utf8_lossy(&remained[len..len + CRLF_LEN])
+        )));
+    }
+    Ok(())
+}
+
+fn check_line(s: &str) -> Result<(), RespError> {
+    if s.contains(['\r', '\n']) {
+        return Err(RespError::InvalidFrame(format!(
+            "Simple string or error must not contain CR or LF: {:?}",
+            s
+        )));
+    }
+    Ok(())
+}
+
+// the text of a simple string or error, invalid utf-8 is replaced unless `strict`
+fn decode_text(data: Vec<u8>, strict: bool) -> Result<String, RespError> {
+    match String::from_utf8(data) {
+        Ok(s) => Ok(s),
+        Err(e) if strict => Err(e.into()),
+        Err(e) => Ok(String::from_utf8_lossy(e.as_bytes()).into_owned()),
     }
 }
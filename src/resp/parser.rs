@@ -0,0 +1,701 @@
+use bytes::{Buf, BytesMut};
+
+use crate::resp::{
+    decode_text, extract_simple_data_end_index, find_ctrl_index,
+    inline::{decode_inline, is_inline},
+    parse_length, BigNumber, BulkError, BulkNullString, BulkString, RawSimpleError,
+    RawSimpleString, RespAttribute, RespDecode, RespError, RespFrame, RespFrameArray, RespLimits,
+    RespMap, RespNullArray, RespPush, RespSet, SimpleError, SimpleNull, SimpleString,
+    VerbatimString, CRLF_LEN,
+};
+
+/// A resumable RESP parser.
+///
+/// Every byte is looked at once: complete elements are consumed from the buffer as soon as
+/// they arrive and partially received aggregates are kept on an internal stack, so a call
+/// that returns [`RespError::NotComplete`] picks up exactly where it stopped once more data
+/// has been appended to the buffer.
+#[derive(Debug, Default)]
+pub struct RespParser {
+    stack: Vec<PendingAggregate>,
+    limits: RespLimits,
+}
+
+// never trust a length from the wire for preallocation
+const MAX_PREALLOC: usize = 4096;
+
+#[derive(Debug)]
+struct PendingAggregate {
+    kind: AggregateKind,
+    expected: usize,
+    frames: Vec<RespFrame>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum AggregateKind {
+    Array,
+    Map,
+    Set,
+    Push,
+    Attribute,
+}
+
+enum Token {
+    Frame(RespFrame),
+    Aggregate(AggregateKind, usize),
+}
+
+// where `scan_frame` found the frame at the start of a buffer to end
+enum Scan {
+    Complete(usize),
+    NotComplete,
+    // something the parser will reject, left to it to report
+    Invalid,
+}
+
+impl RespParser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_limits(limits: RespLimits) -> Self {
+        RespParser {
+            stack: Vec::new(),
+            limits,
+        }
+    }
+
+    /// Parses the next frame from `buf`, consuming the bytes of every complete element.
+    ///
+    /// On [`RespError::NotComplete`] the partial frame is kept in the parser; append more data
+    /// to the same buffer and call `parse` again. Any other error resets the parser.
+    pub fn parse(&mut self, buf: &mut BytesMut) -> Result<RespFrame, RespError> {
+        let ret = self.run(buf);
+        if let Err(e) = &ret {
+            if *e != RespError::NotComplete {
+                self.stack.clear();
+            }
+        }
+        ret
+    }
+
+    /// Parses the next request sent by a client. Like redis, anything that does not start
+    /// with `*` is treated as an inline command and returned as an array of bulk strings.
+    pub fn parse_request(&mut self, buf: &mut BytesMut) -> Result<RespFrame, RespError> {
+        if !self.is_partial() && is_inline(buf) {
+            return decode_inline(buf, self.limits.max_inline_len).map(RespFrame::Array);
+        }
+        self.parse(buf)
+    }
+
+    /// Returns true if a frame has been partially parsed and is waiting for more data.
+    pub fn is_partial(&self) -> bool {
+        !self.stack.is_empty()
+    }
+
+    fn run(&mut self, input: &mut BytesMut) -> Result<RespFrame, RespError> {
+        loop {
+            let token = next_token(input, &self.limits)?;
+            let mut frame = match token {
+                Token::Frame(frame) => frame,
+                Token::Aggregate(kind, len) => {
+                    let pending = PendingAggregate::new(kind, len);
+                    if pending.is_full() {
+                        pending.into_frame()
+                    } else {
+                        if self.stack.len() >= self.limits.max_depth {
+                            return Err(RespError::NestingTooDeep(self.limits.max_depth));
+                        }
+                        self.stack.push(pending);
+                        continue;
+                    }
+                }
+            };
+
+            // hand the finished frame to its parent, finishing parents on the way up
+            loop {
+                let Some(top) = self.stack.last_mut() else {
+                    return Ok(frame);
+                };
+                top.frames.push(frame);
+                if !top.is_full() {
+                    break;
+                }
+                frame = self.stack.pop().expect("stack is not empty").into_frame();
+            }
+        }
+    }
+}
+
+/// Returns how many bytes the complete frame starting with `prefix` at the start of `buf`
+/// occupies, without decoding it.
+pub(crate) fn frame_len(buf: &[u8], prefix: &str, limits: &RespLimits) -> Result<usize, RespError> {
+    if buf.is_empty() {
+        return Err(RespError::NotComplete);
+    }
+    if !buf.starts_with(prefix.as_bytes()) {
+        return Err(RespError::InvalidFrame(format!("Invalid frame: {:?}", buf)));
+    }
+    match scan_frame(buf, limits) {
+        Scan::Complete(len) => Ok(len),
+        Scan::NotComplete => Err(RespError::NotComplete),
+        // let the parser tell what is wrong, on a copy since it consumes what it parses
+        Scan::Invalid => {
+            let mut copy = BytesMut::from(buf);
+            RespParser::with_limits(limits.clone()).parse(&mut copy)?;
+            Ok(buf.len() - copy.len())
+        }
+    }
+}
+
+/// Decodes the frame starting with `prefix` at the start of `buf`, splitting it off only
+/// once it is complete, so bulk string payloads still point into `buf`.
+///
+/// Without a parser keeping state between calls, every call on a partial frame walks the
+/// lines of the elements that have arrived so far again, bulk payloads are skipped. Use
+/// [`RespParser`] to read frames arriving in many pieces, e.g. from a socket.
+pub(crate) fn decode_frame(
+    buf: &mut BytesMut,
+    prefix: &str,
+    limits: &RespLimits,
+) -> Result<RespFrame, RespError> {
+    let len = frame_len(buf, prefix, limits)?;
+    RespParser::with_limits(limits.clone()).parse(&mut buf.split_to(len))
+}
+
+// Finds where the frame at the start of `buf` ends by reading the line of every element,
+// skipping bulk payloads by their length and building nothing.
+fn scan_frame(buf: &[u8], limits: &RespLimits) -> Scan {
+    // the elements each partially scanned aggregate is still waiting for
+    let mut pending: Vec<usize> = Vec::new();
+    let mut pos = 0;
+    loop {
+        let rest = &buf[pos..];
+        let Some(&prefix) = rest.first() else {
+            return Scan::NotComplete;
+        };
+        let window = &rest[..rest.len().min(limits.max_inline_len + CRLF_LEN)];
+        let Some(end) = find_ctrl_index(window, 1) else {
+            return match window.len() == limits.max_inline_len + CRLF_LEN {
+                true => Scan::Invalid,
+                false => Scan::NotComplete,
+            };
+        };
+        let line = std::str::from_utf8(&rest[1..end]).unwrap_or_default();
+        pos += end + CRLF_LEN;
+
+        let children = match prefix {
+            b'+' | b'-' | b':' | b'_' | b'#' | b',' | b'(' => 0,
+            b'$' if line == "-1" => 0,
+            b'$' | b'!' | b'=' => match line.parse::<usize>() {
+                Ok(len) if len <= limits.max_bulk_len => {
+                    pos += len + CRLF_LEN;
+                    if pos > buf.len() {
+                        return Scan::NotComplete;
+                    }
+                    0
+                }
+                _ => return Scan::Invalid,
+            },
+            b'*' if line == "-1" => 0,
+            b'*' | b'%' | b'~' | b'>' | b'|' => match line.parse::<usize>() {
+                Ok(len) if len <= limits.max_aggregate_len => match prefix {
+                    b'%' => len * 2,
+                    b'|' => len * 2 + 1,
+                    _ => len,
+                },
+                _ => return Scan::Invalid,
+            },
+            _ => return Scan::Invalid,
+        };
+        if children > 0 {
+            if pending.len() >= limits.max_depth {
+                return Scan::Invalid;
+            }
+            pending.push(children);
+            continue;
+        }
+
+        // count the finished element against its parents, finishing them on the way up
+        loop {
+            let Some(left) = pending.last_mut() else {
+                return Scan::Complete(pos);
+            };
+            *left -= 1;
+            if *left > 0 {
+                break;
+            }
+            pending.pop();
+        }
+    }
+}
+
+impl PendingAggregate {
+    fn new(kind: AggregateKind, len: usize) -> Self {
+        let expected = match kind {
+            AggregateKind::Array | AggregateKind::Set | AggregateKind::Push => len,
+            AggregateKind::Map => len * 2,
+            // the attribute pairs are followed by the frame they are attached to
+            AggregateKind::Attribute => len * 2 + 1,
+        };
+        PendingAggregate {
+            kind,
+            expected,
+            frames: Vec::with_capacity(expected.min(MAX_PREALLOC)),
+        }
+    }
+
+    fn is_full(&self) -> bool {
+        self.frames.len() == self.expected
+    }
+
+    fn into_frame(mut self) -> RespFrame {
+        match self.kind {
+            AggregateKind::Array => RespFrameArray::new(self.frames).into(),
+            AggregateKind::Set => RespSet::new(self.frames).into(),
+            AggregateKind::Push => RespPush::new(self.frames).into(),
+            AggregateKind::Map => RespMap::new(into_pairs(self.frames)).into(),
+            AggregateKind::Attribute => {
+                let frame = self.frames.pop().expect("attribute has a frame");
+                RespAttribute::new(into_pairs(self.frames), frame).into()
+            }
+        }
+    }
+}
+
+fn into_pairs(frames: Vec<RespFrame>) -> Vec<(RespFrame, RespFrame)> {
+    let mut pairs = Vec::with_capacity(frames.len() / 2);
+    let mut iter = frames.into_iter();
+    while let (Some(key), Some(value)) = (iter.next(), iter.next()) {
+        pairs.push((key, value));
+    }
+    pairs
+}
+
+fn next_token(input: &mut BytesMut, limits: &RespLimits) -> Result<Token, RespError> {
+    let Some(&prefix) = input.first() else {
+        return Err(RespError::NotComplete);
+    };
+    check_line_len(input, limits.max_inline_len)?;
+    let token = match prefix {
+        b'+' => {
+            let raw = take_frame::<RawSimpleString>(input)?;
+            Token::Frame(SimpleString(decode_text(raw.0, limits.strict_utf8)?).into())
+        }
+        b'-' => {
+            let raw = take_frame::<RawSimpleError>(input)?;
+            Token::Frame(SimpleError(decode_text(raw.0, limits.strict_utf8)?).into())
+        }
+        b':' => Token::Frame(take_frame::<i64>(input)?.into()),
+        b'_' => Token::Frame(take_frame::<SimpleNull>(input)?.into()),
+        b'#' => Token::Frame(take_frame::<bool>(input)?.into()),
+        b',' => Token::Frame(take_frame::<f64>(input)?.into()),
+        b'(' => Token::Frame(take_big_number(input, limits)?.into()),
+        b'!' => {
+            check_bulk_len(input, "!", limits)?;
+            Token::Frame(take_frame::<BulkError>(input)?.into())
+        }
+        b'=' => {
+            check_bulk_len(input, "=", limits)?;
+            Token::Frame(take_frame::<VerbatimString>(input)?.into())
+        }
+        b'$' => match take_header(input, "$")? {
+            Some(len) if len > limits.max_bulk_len => return Err(RespError::BulkTooLong(len)),
+            Some(_) => Token::Frame(take_frame::<BulkString>(input)?.into()),
+            None => Token::Frame(BulkNullString.into()),
+        },
+        b'*' => match take_header(input, "*")? {
+            Some(len) => Token::Aggregate(AggregateKind::Array, check_aggregate_len(len, limits)?),
+            None => Token::Frame(RespNullArray.into()),
+        },
+        b'%' => Token::Aggregate(AggregateKind::Map, take_length(input, "%", limits)?),
+        b'~' => Token::Aggregate(AggregateKind::Set, take_length(input, "~", limits)?),
+        b'>' => Token::Aggregate(AggregateKind::Push, take_length(input, ">", limits)?),
+        b'|' => Token::Aggregate(AggregateKind::Attribute, take_length(input, "|", limits)?),
+        // only the offending byte, the rest of the buffer may hold other requests
+        _ => {
+            return Err(RespError::InvalidFrameType(format!(
+                "unknown frame type: {:?}",
+                prefix as char
+            )))
+        }
+    };
+    Ok(token)
+}
+
+fn take_frame<T: RespDecode>(input: &mut BytesMut) -> Result<T, RespError> {
+    let len = T::expect_length(&input[..])?;
+    if input.len() < len {
+        return Err(RespError::NotComplete);
+    }
+    T::decode(&mut input.split_to(len))
+}
+
+// like `take_frame`, but bounds how long the number gets once its exponent is expanded
+fn take_big_number(input: &mut BytesMut, limits: &RespLimits) -> Result<BigNumber, RespError> {
+    let end = extract_simple_data_end_index(input, BigNumber::PREFIX)?;
+    let data = input.split_to(end + CRLF_LEN);
+    let s = String::from_utf8_lossy(&data[BigNumber::PREFIX.len()..end]);
+    BigNumber::parse(&s, limits.max_big_number_len)
+}
+
+// Reads a `<prefix><len>\r\n` header that may also be the `-1` null marker. The header of
+// a null is consumed, while the header of a bulk string is left for its own decoder.
+fn take_header(input: &mut BytesMut, prefix: &str) -> Result<Option<usize>, RespError> {
+    let end = extract_simple_data_end_index(input, prefix)?;
+    let s = String::from_utf8_lossy(&input[prefix.len()..end]);
+    if s == "-1" {
+        input.advance(end + CRLF_LEN);
+        return Ok(None);
+    }
+    let len = s.parse()?;
+    if prefix != "$" {
+        input.advance(end + CRLF_LEN);
+    }
+    Ok(Some(len))
+}
+
+fn take_length(
+    input: &mut BytesMut,
+    prefix: &str,
+    limits: &RespLimits,
+) -> Result<usize, RespError> {
+    let end = extract_simple_data_end_index(input, prefix)?;
+    let len = String::from_utf8_lossy(&input[prefix.len()..end]).parse()?;
+    input.advance(end + CRLF_LEN);
+    check_aggregate_len(len, limits)
+}
+
+fn check_aggregate_len(len: usize, limits: &RespLimits) -> Result<usize, RespError> {
+    if len > limits.max_aggregate_len {
+        return Err(RespError::AggregateTooLong(len));
+    }
+    Ok(len)
+}
+
+fn check_bulk_len(buf: &[u8], prefix: &str, limits: &RespLimits) -> Result<(), RespError> {
+    let (_, len) = parse_length(buf, prefix)?;
+    if len > limits.max_bulk_len {
+        return Err(RespError::BulkTooLong(len));
+    }
+    Ok(())
+}
+
+// Rejects an element whose first line is longer than allowed, whether its CRLF has arrived
+// or not. Looks at a bounded window only, so it never rescans a large bulk payload.
+fn check_line_len(buf: &[u8], max_inline_len: usize) -> Result<(), RespError> {
+    let window = &buf[..buf.len().min(max_inline_len + CRLF_LEN)];
+    if window.len() == max_inline_len + CRLF_LEN && find_ctrl_index(window, 0).is_none() {
+        return Err(RespError::LineTooLong(max_inline_len));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::resp::*;
+    use anyhow::Result;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_parser_resumes_partial_frame() -> Result<()> {
+        let mut parser = RespParser::new();
+        let mut buf = BytesMut::new();
+
+        buf.extend_from_slice(b"*3\r\n$3\r\nset\r\n*1\r\n$5\r\nhel");
+        assert_eq!(parser.parse(&mut buf).unwrap_err(), RespError::NotComplete);
+        assert!(parser.is_partial());
+        // only the incomplete bulk string is left in the buffer
+        assert_eq!(buf.as_ref(), b"$5\r\nhel");
+
+        buf.extend_from_slice(b"lo\r\n:1");
+        assert_eq!(parser.parse(&mut buf).unwrap_err(), RespError::NotComplete);
+
+        buf.extend_from_slice(b"\r\n+OK\r\n");
+        let frame = parser.parse(&mut buf)?;
+        assert_eq!(
+            frame,
+            RespFrameArray::new(vec![
+                b"set".into(),
+                RespFrameArray::new(vec![b"hello".into()]).into(),
+                1.into(),
+            ])
+            .into()
+        );
+        assert!(!parser.is_partial());
+
+        let frame = parser.parse(&mut buf)?;
+        assert_eq!(frame, SimpleString::new("OK").into());
+        assert!(buf.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parser_large_mset_in_segments() -> Result<()> {
+        let count = 100_000;
+        let mut data = format!("*{}\r\n$4\r\nMSET\r\n", count * 2 + 1).into_bytes();
+        for i in 0..count {
+            let key = format!("key:{}", i);
+            let value = format!("value:{}", i);
+            data.extend_from_slice(format!("${}\r\n{}\r\n", key.len(), key).as_bytes());
+            data.extend_from_slice(format!("${}\r\n{}\r\n", value.len(), value).as_bytes());
+        }
+
+        let mut parser = RespParser::new();
+        let mut buf = BytesMut::new();
+        let mut frame = None;
+        for segment in data.chunks(1460) {
+            buf.extend_from_slice(segment);
+            match parser.parse(&mut buf) {
+                Ok(f) => frame = Some(f),
+                Err(RespError::NotComplete) => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        let Some(RespFrame::Array(frame)) = frame else {
+            panic!("expect an array frame");
+        };
+        assert_eq!(frame.len(), count * 2 + 1);
+        assert_eq!(frame[0], b"MSET".into());
+        assert_eq!(frame[count * 2], b"value:99999".into());
+        assert!(buf.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_large_frame_in_chunks() -> Result<()> {
+        let payload = vec![b'x'; 32 * 1024 * 1024];
+        let mut data = b"*2\r\n$3\r\nset\r\n".to_vec();
+        data.extend_from_slice(format!("${}\r\n", payload.len()).as_bytes());
+        data.extend_from_slice(&payload);
+        data.extend_from_slice(b"\r\n");
+
+        // every call on the partial frame only reads its headers, never the payload so far
+        let start = std::time::Instant::now();
+        let mut buf = BytesMut::new();
+        let mut frame = None;
+        for chunk in data.chunks(4096) {
+            buf.extend_from_slice(chunk);
+            match RespFrame::decode(&mut buf) {
+                Ok(f) => frame = Some(f),
+                Err(RespError::NotComplete) => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+        assert!(start.elapsed() < std::time::Duration::from_secs(5));
+
+        let Some(RespFrame::Array(frame)) = frame else {
+            panic!("expect an array frame");
+        };
+        assert_eq!(frame[1], BulkString::new(payload).into());
+        assert!(buf.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_parser_bulk_strings_are_zero_copy() -> Result<()> {
+        let mut parser = RespParser::new();
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*2\r\n$3\r\nget\r\n$5\r\nhello\r\n");
+        let payload = buf[17..].as_ptr();
+
+        let RespFrame::Array(frame) = parser.parse(&mut buf)? else {
+            panic!("expect an array frame");
+        };
+        let RespFrame::BulkStrings(value) = &frame[1] else {
+            panic!("expect a bulk string");
+        };
+        assert_eq!(value.as_ptr(), payload);
+        assert_eq!(&value[..], b"hello");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_bulk_strings_are_zero_copy() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*2\r\n$3\r\nget\r\n$5\r\nhello\r\n$2\r\nhi\r\n");
+        let payload = buf[17..].as_ptr();
+        let next = buf[28..].as_ptr();
+
+        let RespFrame::Array(frame) = RespFrame::decode(&mut buf)? else {
+            panic!("expect an array frame");
+        };
+        let RespFrame::BulkStrings(value) = &frame[1] else {
+            panic!("expect a bulk string");
+        };
+        assert_eq!(value.as_ptr(), payload);
+
+        let value = BulkString::decode(&mut buf)?;
+        assert_eq!(value.as_ptr(), next);
+        assert_eq!(&value[..], b"hi");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parser_resets_after_error() -> Result<()> {
+        let mut parser = RespParser::new();
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*2\r\n:1\r\n?\r\n");
+        assert_eq!(
+            parser.parse(&mut buf),
+            Err(RespError::InvalidFrameType(
+                "unknown frame type: '?'".to_string()
+            ))
+        );
+        assert!(!parser.is_partial());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parser_limits() {
+        let limits = RespLimits {
+            max_bulk_len: 8,
+            max_aggregate_len: 4,
+            max_depth: 2,
+            max_inline_len: 16,
+            ..Default::default()
+        };
+        let cases: [(&[u8], RespError); 7] = [
+            (b"$9\r\n", RespError::BulkTooLong(9)),
+            (b"!100\r\n", RespError::BulkTooLong(100)),
+            (b"*5\r\n", RespError::AggregateTooLong(5)),
+            (b"%5\r\n", RespError::AggregateTooLong(5)),
+            (b"*1\r\n*1\r\n*1\r\n", RespError::NestingTooDeep(2)),
+            (b"+aaaaaaaaaaaaaaaaaaaa", RespError::LineTooLong(16)),
+            (b"*11111111111111111111111", RespError::LineTooLong(16)),
+        ];
+        for (data, expected) in cases {
+            let mut parser = RespParser::with_limits(limits.clone());
+            let mut buf = BytesMut::from(data);
+            assert_eq!(parser.parse(&mut buf).unwrap_err(), expected);
+        }
+
+        let mut parser = RespParser::with_limits(limits);
+        let mut buf = BytesMut::from(&b"*2\r\n*1\r\n$8\r\n12345678\r\n+aaaaaaaaaaaaaaa"[..]);
+        assert_eq!(parser.parse(&mut buf).unwrap_err(), RespError::NotComplete);
+        buf.extend_from_slice(b"\r\n");
+        assert!(parser.parse(&mut buf).is_ok());
+    }
+
+    #[test]
+    fn test_parser_limits_complete_lines() {
+        let limits = RespLimits {
+            max_inline_len: 16,
+            ..Default::default()
+        };
+        let line = format!("+{}\r\n", "a".repeat(100));
+        let cases = [
+            line.clone(),
+            format!("*1\r\n{}", line),
+            format!("-{}\r\n", "e".repeat(100)),
+            format!(":{}\r\n", "1".repeat(17)),
+            format!("${}\r\n", "0".repeat(16)),
+            format!("*{}1\r\n", "0".repeat(16)),
+        ];
+        for data in cases {
+            let mut parser = RespParser::with_limits(limits.clone());
+            let mut buf = BytesMut::from(data.as_bytes());
+            assert_eq!(parser.parse(&mut buf), Err(RespError::LineTooLong(16)));
+
+            let mut buf = BytesMut::from(data.as_bytes());
+            assert_eq!(
+                RespFrame::decode_with_limits(&mut buf, &limits),
+                Err(RespError::LineTooLong(16))
+            );
+            assert_eq!(buf.len(), data.len());
+        }
+    }
+
+    #[test]
+    fn test_parser_strict_utf8() -> Result<()> {
+        let data = &b"*2\r\n+caf\xc3\xa9\r\n-ERR \xff\r\n"[..];
+
+        let mut buf = BytesMut::from(data);
+        let frame = RespParser::new().parse(&mut buf)?;
+        assert_eq!(
+            frame,
+            RespFrameArray::new(vec![
+                SimpleString::new("café").into(),
+                SimpleError::new("ERR \u{fffd}").into(),
+            ])
+            .into()
+        );
+
+        let limits = RespLimits {
+            strict_utf8: true,
+            ..Default::default()
+        };
+        let mut buf = BytesMut::from(data);
+        assert!(matches!(
+            RespParser::with_limits(limits.clone()).parse(&mut buf),
+            Err(RespError::Utf8Error(_))
+        ));
+        let mut buf = BytesMut::from(data);
+        assert!(matches!(
+            RespFrame::decode_with_limits(&mut buf, &limits),
+            Err(RespError::Utf8Error(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_huge_aggregate_length() {
+        let mut buf = BytesMut::from(&b"*9999999999999\r\n"[..]);
+        assert_eq!(
+            RespFrame::decode(&mut buf).unwrap_err(),
+            RespError::AggregateTooLong(9999999999999)
+        );
+
+        // allowed, but the length must not be used to preallocate
+        let mut buf = BytesMut::from(&b"*2147483647\r\n:1\r\n"[..]);
+        assert_eq!(
+            RespFrame::decode(&mut buf).unwrap_err(),
+            RespError::NotComplete
+        );
+    }
+
+    #[test]
+    fn test_decode_nested_and_empty_aggregates() -> Result<()> {
+        let mut buf = BytesMut::new();
+        buf.extend_from_slice(b"*0\r\n");
+        let frame = RespFrame::decode(&mut buf)?;
+        assert_eq!(frame, RespFrameArray::new(vec![]).into());
+
+        buf.extend_from_slice(b"*2\r\n*-1\r\n$-1\r\n");
+        let frame = RespFrame::decode(&mut buf)?;
+        assert_eq!(
+            frame,
+            RespFrameArray::new(vec![RespNullArray.into(), BulkNullString.into()]).into()
+        );
+
+        buf.extend_from_slice(b"*1\r\n*1\r\n*1\r\n");
+        assert_eq!(
+            RespFrame::decode(&mut buf).unwrap_err(),
+            RespError::NotComplete
+        );
+        // a stateless decode leaves the buffer untouched until the frame is complete
+        assert_eq!(buf.as_ref(), b"*1\r\n*1\r\n*1\r\n");
+
+        buf.extend_from_slice(b"$2\r\nhi\r\n");
+        let frame = RespFrame::decode(&mut buf)?;
+        assert_eq!(
+            frame,
+            RespFrameArray::new(vec![RespFrameArray::new(vec![RespFrameArray::new(vec![
+                b"hi".into()
+            ])
+            .into()])
+            .into()])
+            .into()
+        );
+        assert!(buf.is_empty());
+
+        Ok(())
+    }
+}
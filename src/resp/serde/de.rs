@@ -0,0 +1,419 @@
+use bigdecimal::ToPrimitive;
+use serde::{
+    de::{
+        self,
+        value::{MapDeserializer, SeqDeserializer},
+        DeserializeOwned, DeserializeSeed, EnumAccess, IntoDeserializer, VariantAccess, Visitor,
+    },
+    forward_to_deserialize_any,
+};
+
+use super::RespSerdeError;
+use crate::resp::{BigNumber, RespFrame};
+
+/// Deserializes a value from a [`RespFrame`] tree, the inverse of [`to_frame`].
+///
+/// Error replies become [`RespSerdeError::ErrorReply`]. Numbers and booleans can also be
+/// read from strings and integers the way RESP2 servers send them, e.g. `$2\r\n42\r\n`.
+///
+/// [`to_frame`]: super::to_frame
+pub fn from_frame<T: DeserializeOwned>(frame: RespFrame) -> Result<T, RespSerdeError> {
+    T::deserialize(frame)
+}
+
+impl IntoDeserializer<'_, RespSerdeError> for RespFrame {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+fn is_null(frame: &RespFrame) -> bool {
+    matches!(
+        frame,
+        RespFrame::Nulls(_) | RespFrame::BulkNullStrings(_) | RespFrame::NullArray(_)
+    )
+}
+
+// the text of a frame that is sent as a string
+fn text(frame: &RespFrame) -> Option<&[u8]> {
+    match frame {
+        RespFrame::Strings(s) => Some(s.as_bytes()),
+        RespFrame::BulkStrings(s) => Some(s),
+        RespFrame::VerbatimStrings(s) => Some(s),
+        _ => None,
+    }
+}
+
+fn visit_seq<'de, V, I>(iter: I, visitor: V) -> Result<V::Value, RespSerdeError>
+where
+    V: Visitor<'de>,
+    I: Iterator,
+    I::Item: IntoDeserializer<'de, RespSerdeError>,
+{
+    let mut deserializer = SeqDeserializer::new(iter);
+    let value = visitor.visit_seq(&mut deserializer)?;
+    deserializer.end()?;
+    Ok(value)
+}
+
+fn visit_big_number<'de, V: Visitor<'de>>(
+    num: BigNumber,
+    visitor: V,
+) -> Result<V::Value, RespSerdeError> {
+    if num.0.is_integer() {
+        if let Some(i) = num.0.to_i64() {
+            return visitor.visit_i64(i);
+        }
+        if let Some(u) = num.0.to_u64() {
+            return visitor.visit_u64(u);
+        }
+    }
+    visitor.visit_string(num.to_plain_string())
+}
+
+impl RespFrame {
+    fn deserialize_number<'de, V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, RespSerdeError> {
+        if let RespFrame::BigNumbers(num) = &self {
+            if !num.0.is_integer() {
+                if let Some(f) = num.0.to_f64() {
+                    return visitor.visit_f64(f);
+                }
+            }
+        }
+        let Some(text) = text(&self) else {
+            return de::Deserializer::deserialize_any(self, visitor);
+        };
+        let text = String::from_utf8_lossy(text);
+        if let Ok(i) = text.parse::<i64>() {
+            visitor.visit_i64(i)
+        } else if let Ok(u) = text.parse::<u64>() {
+            visitor.visit_u64(u)
+        } else if let Ok(f) = text.parse::<f64>() {
+            visitor.visit_f64(f)
+        } else {
+            Err(de::Error::invalid_value(
+                de::Unexpected::Str(&text),
+                &"a number",
+            ))
+        }
+    }
+}
+
+macro_rules! deserialize_numbers {
+    ($($method:ident)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, RespSerdeError> {
+                self.deserialize_number(visitor)
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for RespFrame {
+    type Error = RespSerdeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, RespSerdeError> {
+        match self {
+            RespFrame::Strings(s) => visitor.visit_string(s.0),
+            RespFrame::Errors(e) => Err(RespSerdeError::ErrorReply(e.0)),
+            RespFrame::BulkErrors(e) => Err(RespSerdeError::ErrorReply(
+                String::from_utf8_lossy(&e).into_owned(),
+            )),
+            RespFrame::Integers(i) => visitor.visit_i64(i),
+            RespFrame::Nulls(_) | RespFrame::BulkNullStrings(_) | RespFrame::NullArray(_) => {
+                visitor.visit_unit()
+            }
+            RespFrame::Booleans(b) => visitor.visit_bool(b),
+            RespFrame::Doubles(f) => visitor.visit_f64(f),
+            RespFrame::BigNumbers(num) => visit_big_number(num, visitor),
+            RespFrame::BulkStrings(s) => visitor.visit_byte_buf(s.to_vec()),
+            RespFrame::VerbatimStrings(s) => visitor.visit_byte_buf(s.to_vec()),
+            RespFrame::Array(frames) => visit_seq(frames.0.into_iter(), visitor),
+            RespFrame::Set(frames) => visit_seq(frames.into_iter(), visitor),
+            RespFrame::Push(frames) => visit_seq(frames.0.into_iter(), visitor),
+            RespFrame::Map(pairs) => {
+                let mut deserializer = MapDeserializer::new(pairs.0.into_iter());
+                let value = visitor.visit_map(&mut deserializer)?;
+                deserializer.end()?;
+                Ok(value)
+            }
+            RespFrame::Attribute(attribute) => attribute.into_frame().deserialize_any(visitor),
+        }
+    }
+
+    deserialize_numbers! {
+        deserialize_i8 deserialize_i16 deserialize_i32 deserialize_i64 deserialize_i128
+        deserialize_u8 deserialize_u16 deserialize_u32 deserialize_u64 deserialize_u128
+        deserialize_f32 deserialize_f64
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, RespSerdeError> {
+        match self {
+            RespFrame::Integers(0) => visitor.visit_bool(false),
+            RespFrame::Integers(1) => visitor.visit_bool(true),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, RespSerdeError> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, RespSerdeError> {
+        match text(&self) {
+            Some(text) => match String::from_utf8(text.to_vec()) {
+                Ok(s) => visitor.visit_string(s),
+                Err(_) => self.deserialize_any(visitor),
+            },
+            None => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, RespSerdeError> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, RespSerdeError> {
+        self.deserialize_byte_buf(visitor)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, RespSerdeError> {
+        match self {
+            RespFrame::Strings(s) => visitor.visit_byte_buf(s.0.into_bytes()),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    // lets byte strings deserialize into `Vec<u8>`, whose visitor only accepts sequences
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, RespSerdeError> {
+        match text(&self) {
+            Some(bytes) => visit_seq(bytes.iter().copied(), visitor),
+            None => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, RespSerdeError> {
+        if is_null(&self) {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, RespSerdeError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, RespSerdeError> {
+        match self {
+            RespFrame::Map(pairs) if pairs.len() == 1 => {
+                let (variant, value) = pairs.0.into_iter().next().expect("map has one entry");
+                visitor.visit_enum(EnumDeserializer {
+                    variant,
+                    value: Some(value),
+                })
+            }
+            frame if text(&frame).is_some() => visitor.visit_enum(EnumDeserializer {
+                variant: frame,
+                value: None,
+            }),
+            frame => Err(de::Error::invalid_type(
+                de::Unexpected::Other(&format!("{:?}", frame)),
+                &"a string or a map with a single entry",
+            )),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        char unit unit_struct tuple tuple_struct map struct ignored_any
+    }
+}
+
+struct EnumDeserializer {
+    variant: RespFrame,
+    value: Option<RespFrame>,
+}
+
+struct VariantDeserializer {
+    value: Option<RespFrame>,
+}
+
+impl<'de> EnumAccess<'de> for EnumDeserializer {
+    type Error = RespSerdeError;
+    type Variant = VariantDeserializer;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, VariantDeserializer), RespSerdeError> {
+        let variant = seed.deserialize(self.variant)?;
+        Ok((variant, VariantDeserializer { value: self.value }))
+    }
+}
+
+impl<'de> VariantAccess<'de> for VariantDeserializer {
+    type Error = RespSerdeError;
+
+    fn unit_variant(self) -> Result<(), RespSerdeError> {
+        match self.value {
+            Some(frame) if !is_null(&frame) => Err(de::Error::invalid_type(
+                de::Unexpected::Other(&format!("{:?}", frame)),
+                &"unit variant",
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> Result<T::Value, RespSerdeError> {
+        match self.value {
+            Some(frame) => seed.deserialize(frame),
+            None => Err(de::Error::invalid_type(
+                de::Unexpected::UnitVariant,
+                &"newtype variant",
+            )),
+        }
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, RespSerdeError> {
+        match self.value {
+            Some(frame) => de::Deserializer::deserialize_seq(frame, visitor),
+            None => Err(de::Error::invalid_type(
+                de::Unexpected::UnitVariant,
+                &"tuple variant",
+            )),
+        }
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, RespSerdeError> {
+        match self.value {
+            Some(frame) => de::Deserializer::deserialize_map(frame, visitor),
+            None => Err(de::Error::invalid_type(
+                de::Unexpected::UnitVariant,
+                &"struct variant",
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use ::serde::{Deserialize, Serialize};
+    use bytes::BytesMut;
+
+    use crate::resp::*;
+    use anyhow::Result;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct User {
+        name: String,
+        age: u8,
+        email: Option<String>,
+        scores: Vec<f64>,
+        attrs: HashMap<String, i64>,
+        role: Role,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    enum Role {
+        Admin,
+        Guest { expires: u64 },
+        Member(String),
+    }
+
+    #[test]
+    fn test_struct_round_trip_through_codec() -> Result<()> {
+        for role in [
+            Role::Admin,
+            Role::Guest { expires: 3600 },
+            Role::Member("ops".to_string()),
+        ] {
+            let user = User {
+                name: "alice".to_string(),
+                age: 30,
+                email: Some("alice@example.com".to_string()),
+                scores: vec![1.5, -2.0],
+                attrs: HashMap::from([("visits".to_string(), 42)]),
+                role,
+            };
+
+            let mut buf = BytesMut::from(&to_frame(&user)?.encode()[..]);
+            let frame = RespFrame::decode(&mut buf)?;
+            let decoded: User = from_frame(frame)?;
+            assert_eq!(decoded, user);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_resp2_style_frames() -> Result<()> {
+        let n: u32 = from_frame(b"42".into())?;
+        assert_eq!(n, 42);
+
+        let f: f64 = from_frame(SimpleString::new("3.5").into())?;
+        assert_eq!(f, 3.5);
+
+        let b: bool = from_frame(1.into())?;
+        assert!(b);
+
+        let none: Option<String> = from_frame(BulkNullString.into())?;
+        assert_eq!(none, None);
+
+        let (key, value): (String, Vec<u8>) =
+            from_frame(RespFrameArray::new(vec![b"key".into(), b"\xff".into()]).into())?;
+        assert_eq!((key.as_str(), value), ("key", vec![0xff]));
+
+        let big: u64 = from_frame(BigNumber::new("18446744073709551615")?.into())?;
+        assert_eq!(big, u64::MAX);
+
+        let set: Vec<i64> = from_frame(RespSet::new(vec![1.into(), 2.into()]).into())?;
+        assert_eq!(set, vec![1, 2]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_frame_errors() {
+        let ret: Result<String, _> = from_frame(SimpleError::new("ERR boom").into());
+        assert_eq!(
+            ret.unwrap_err(),
+            RespSerdeError::ErrorReply("ERR boom".to_string())
+        );
+
+        let ret: Result<u8, _> = from_frame(b"abc".into());
+        assert!(matches!(ret.unwrap_err(), RespSerdeError::Custom(_)));
+
+        let ret: Result<(i64, i64), _> =
+            from_frame(RespFrameArray::new(vec![1.into(), 2.into(), 3.into()]).into());
+        assert!(ret.is_err());
+    }
+}
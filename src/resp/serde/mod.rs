@@ -0,0 +1,29 @@
+mod de;
+mod ser;
+
+use std::fmt::Display;
+
+use thiserror::Error;
+
+pub use de::from_frame;
+pub use ser::to_frame;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum RespSerdeError {
+    #[error("{0}")]
+    Custom(String),
+    #[error("Error reply: {0}")]
+    ErrorReply(String),
+}
+
+impl serde::ser::Error for RespSerdeError {
+    fn custom<T: Display>(msg: T) -> Self {
+        RespSerdeError::Custom(msg.to_string())
+    }
+}
+
+impl serde::de::Error for RespSerdeError {
+    fn custom<T: Display>(msg: T) -> Self {
+        RespSerdeError::Custom(msg.to_string())
+    }
+}
@@ -0,0 +1,467 @@
+use std::str::FromStr;
+
+use bigdecimal::BigDecimal;
+use serde::{ser, Serialize};
+
+use super::RespSerdeError;
+use crate::resp::{BigNumber, BulkString, RespFrame, RespFrameArray, RespMap, SimpleNull};
+
+/// Serializes a value into a [`RespFrame`] tree.
+///
+/// Structs and maps become [`RespMap`]s, sequences and tuples become [`RespFrameArray`]s,
+/// strings and bytes become [`BulkString`]s and `None` or `()` becomes [`SimpleNull`].
+/// Enum variants carrying data are a single entry map from the variant name to the data.
+pub fn to_frame<T: Serialize + ?Sized>(value: &T) -> Result<RespFrame, RespSerdeError> {
+    value.serialize(FrameSerializer)
+}
+
+struct FrameSerializer;
+
+pub struct SerializeVec {
+    frames: Vec<RespFrame>,
+}
+
+pub struct SerializeTupleVariant {
+    variant: &'static str,
+    frames: Vec<RespFrame>,
+}
+
+pub struct SerializeMap {
+    pairs: Vec<(RespFrame, RespFrame)>,
+    next_key: Option<RespFrame>,
+}
+
+pub struct SerializeStructVariant {
+    variant: &'static str,
+    pairs: Vec<(RespFrame, RespFrame)>,
+}
+
+fn variant_map(variant: &'static str, value: RespFrame) -> RespFrame {
+    RespMap::new(vec![(BulkString::new(variant).into(), value)]).into()
+}
+
+impl ser::Serializer for FrameSerializer {
+    type Ok = RespFrame;
+    type Error = RespSerdeError;
+
+    type SerializeSeq = SerializeVec;
+    type SerializeTuple = SerializeVec;
+    type SerializeTupleStruct = SerializeVec;
+    type SerializeTupleVariant = SerializeTupleVariant;
+    type SerializeMap = SerializeMap;
+    type SerializeStruct = SerializeMap;
+    type SerializeStructVariant = SerializeStructVariant;
+
+    fn serialize_bool(self, v: bool) -> Result<RespFrame, RespSerdeError> {
+        Ok(v.into())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<RespFrame, RespSerdeError> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<RespFrame, RespSerdeError> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<RespFrame, RespSerdeError> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<RespFrame, RespSerdeError> {
+        Ok(v.into())
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<RespFrame, RespSerdeError> {
+        match i64::try_from(v) {
+            Ok(v) => Ok(v.into()),
+            Err(_) => big_number(v),
+        }
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<RespFrame, RespSerdeError> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<RespFrame, RespSerdeError> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<RespFrame, RespSerdeError> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<RespFrame, RespSerdeError> {
+        self.serialize_i128(v as i128)
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<RespFrame, RespSerdeError> {
+        match i64::try_from(v) {
+            Ok(v) => Ok(v.into()),
+            Err(_) => big_number(v),
+        }
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<RespFrame, RespSerdeError> {
+        self.serialize_f64(v as f64)
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<RespFrame, RespSerdeError> {
+        Ok(v.into())
+    }
+
+    fn serialize_char(self, v: char) -> Result<RespFrame, RespSerdeError> {
+        self.serialize_str(v.encode_utf8(&mut [0; 4]))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<RespFrame, RespSerdeError> {
+        Ok(BulkString::new(v).into())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<RespFrame, RespSerdeError> {
+        Ok(BulkString::new(v).into())
+    }
+
+    fn serialize_none(self) -> Result<RespFrame, RespSerdeError> {
+        Ok(SimpleNull.into())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<RespFrame, RespSerdeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<RespFrame, RespSerdeError> {
+        Ok(SimpleNull.into())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<RespFrame, RespSerdeError> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<RespFrame, RespSerdeError> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<RespFrame, RespSerdeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<RespFrame, RespSerdeError> {
+        Ok(variant_map(variant, value.serialize(self)?))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SerializeVec, RespSerdeError> {
+        Ok(SerializeVec {
+            frames: Vec::with_capacity(len.unwrap_or(0)),
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SerializeVec, RespSerdeError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SerializeVec, RespSerdeError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeTupleVariant, RespSerdeError> {
+        Ok(SerializeTupleVariant {
+            variant,
+            frames: Vec::with_capacity(len),
+        })
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<SerializeMap, RespSerdeError> {
+        Ok(SerializeMap {
+            pairs: Vec::with_capacity(len.unwrap_or(0)),
+            next_key: None,
+        })
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SerializeMap, RespSerdeError> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeStructVariant, RespSerdeError> {
+        Ok(SerializeStructVariant {
+            variant,
+            pairs: Vec::with_capacity(len),
+        })
+    }
+}
+
+fn big_number(v: impl ToString) -> Result<RespFrame, RespSerdeError> {
+    let num =
+        BigDecimal::from_str(&v.to_string()).map_err(|e| RespSerdeError::Custom(e.to_string()))?;
+    Ok(BigNumber(num).into())
+}
+
+impl ser::SerializeSeq for SerializeVec {
+    type Ok = RespFrame;
+    type Error = RespSerdeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(
+        &mut self,
+        value: &T,
+    ) -> Result<(), RespSerdeError> {
+        self.frames.push(to_frame(value)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<RespFrame, RespSerdeError> {
+        Ok(RespFrameArray::new(self.frames).into())
+    }
+}
+
+impl ser::SerializeTuple for SerializeVec {
+    type Ok = RespFrame;
+    type Error = RespSerdeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(
+        &mut self,
+        value: &T,
+    ) -> Result<(), RespSerdeError> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<RespFrame, RespSerdeError> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl ser::SerializeTupleStruct for SerializeVec {
+    type Ok = RespFrame;
+    type Error = RespSerdeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), RespSerdeError> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<RespFrame, RespSerdeError> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl ser::SerializeTupleVariant for SerializeTupleVariant {
+    type Ok = RespFrame;
+    type Error = RespSerdeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), RespSerdeError> {
+        self.frames.push(to_frame(value)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<RespFrame, RespSerdeError> {
+        Ok(variant_map(
+            self.variant,
+            RespFrameArray::new(self.frames).into(),
+        ))
+    }
+}
+
+impl ser::SerializeMap for SerializeMap {
+    type Ok = RespFrame;
+    type Error = RespSerdeError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), RespSerdeError> {
+        self.next_key = Some(to_frame(key)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), RespSerdeError> {
+        let key = self.next_key.take().ok_or_else(|| {
+            RespSerdeError::Custom("serialize_value called before serialize_key".to_string())
+        })?;
+        self.pairs.push((key, to_frame(value)?));
+        Ok(())
+    }
+
+    fn end(self) -> Result<RespFrame, RespSerdeError> {
+        Ok(RespMap::new(self.pairs).into())
+    }
+}
+
+impl ser::SerializeStruct for SerializeMap {
+    type Ok = RespFrame;
+    type Error = RespSerdeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), RespSerdeError> {
+        self.pairs
+            .push((BulkString::new(key).into(), to_frame(value)?));
+        Ok(())
+    }
+
+    fn end(self) -> Result<RespFrame, RespSerdeError> {
+        ser::SerializeMap::end(self)
+    }
+}
+
+impl ser::SerializeStructVariant for SerializeStructVariant {
+    type Ok = RespFrame;
+    type Error = RespSerdeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), RespSerdeError> {
+        self.pairs
+            .push((BulkString::new(key).into(), to_frame(value)?));
+        Ok(())
+    }
+
+    fn end(self) -> Result<RespFrame, RespSerdeError> {
+        Ok(variant_map(self.variant, RespMap::new(self.pairs).into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use ::serde::Serialize;
+
+    use crate::resp::*;
+    use anyhow::Result;
+
+    #[derive(Serialize)]
+    struct User {
+        name: String,
+        age: u8,
+        email: Option<String>,
+        tags: Vec<&'static str>,
+    }
+
+    #[derive(Serialize)]
+    enum Event {
+        Ping,
+        Move(i64, i64),
+        Rename { to: String },
+    }
+
+    #[test]
+    fn test_struct_to_frame() -> Result<()> {
+        let user = User {
+            name: "alice".to_string(),
+            age: 30,
+            email: None,
+            tags: vec!["admin"],
+        };
+        let frame = to_frame(&user)?;
+        assert_eq!(
+            frame,
+            RespMap::new(vec![
+                (b"name".into(), b"alice".into()),
+                (b"age".into(), 30.into()),
+                (b"email".into(), SimpleNull.into()),
+                (
+                    b"tags".into(),
+                    RespFrameArray::new(vec![b"admin".into()]).into()
+                ),
+            ])
+            .into()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_values_to_frame() -> Result<()> {
+        assert_eq!(to_frame(&true)?, true.into());
+        assert_eq!(to_frame(&1.5)?, 1.5.into());
+        assert_eq!(to_frame(&Some(-7))?, (-7).into());
+        assert_eq!(
+            to_frame(&u64::MAX)?,
+            BigNumber::new("18446744073709551615")?.into()
+        );
+        assert_eq!(
+            to_frame(&serde_bytes_like(b"\x00\xff"))?,
+            BulkString::new(b"\x00\xff").into()
+        );
+        assert_eq!(
+            to_frame(&("a", 1))?,
+            RespFrameArray::new(vec![b"a".into(), 1.into()]).into()
+        );
+
+        let map = BTreeMap::from([(1, "one"), (2, "two")]);
+        assert_eq!(
+            to_frame(&map)?,
+            RespMap::new(vec![(1.into(), b"one".into()), (2.into(), b"two".into())]).into()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_enum_to_frame() -> Result<()> {
+        assert_eq!(to_frame(&Event::Ping)?, b"Ping".into());
+        assert_eq!(
+            to_frame(&Event::Move(1, -1))?,
+            RespMap::new(vec![(
+                b"Move".into(),
+                RespFrameArray::new(vec![1.into(), (-1).into()]).into()
+            )])
+            .into()
+        );
+        assert_eq!(
+            to_frame(&Event::Rename {
+                to: "b".to_string()
+            })?,
+            RespMap::new(vec![(
+                b"Rename".into(),
+                RespMap::new(vec![(b"to".into(), b"b".into())]).into()
+            )])
+            .into()
+        );
+        Ok(())
+    }
+
+    struct Bytes<'a>(&'a [u8]);
+
+    impl Serialize for Bytes<'_> {
+        fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_bytes(self.0)
+        }
+    }
+
+    fn serde_bytes_like(data: &[u8]) -> Bytes<'_> {
+        Bytes(data)
+    }
+}
@@ -1,9 +1,10 @@
 use anyhow::Result;
 use std::str::FromStr;
 
-use bigdecimal::BigDecimal;
+use bigdecimal::{num_bigint::Sign, BigDecimal};
+use bytes::BufMut;
 
-use crate::resp::{RespDecode, RespEncode, RespError, CRLF_LEN};
+use crate::resp::{RespDecode, RespEncode, RespError, RespLimits, CRLF_LEN};
 
 use crate::resp::extract_simple_data_end_index;
 
@@ -11,31 +12,83 @@ use crate::resp::extract_simple_data_end_index;
 pub struct BigNumber(pub BigDecimal);
 
 impl BigNumber {
-    #[allow(unused)]
+    /// Parses a big number, rejecting one longer than the default
+    /// [`RespLimits::max_big_number_len`] when written out.
     pub fn new(s: &str) -> Result<BigNumber, RespError> {
+        Self::parse(s, RespLimits::default().max_big_number_len)
+    }
+
+    pub(crate) fn parse(s: &str, max_len: usize) -> Result<BigNumber, RespError> {
         let num = BigDecimal::from_str(s)?;
+        let len = plain_len(&num);
+        if len > max_len {
+            return Err(RespError::BigNumberTooLong(len));
+        }
         Ok(BigNumber(num))
     }
-}
 
-impl RespEncode for BigNumber {
-    fn encode(self) -> Vec<u8> {
-        let sign = match self.0 >= BigDecimal::from(0) {
+    /// The number with every digit spelled out, e.g. `-0.000001` rather than `-1E-6`.
+    pub fn to_plain_string(&self) -> String {
+        let sign = match self.0.sign() != Sign::Minus {
+            true => "",
+            false => "-",
+        };
+        format!("{}{}", sign, plain_digits(&self.0))
+    }
+
+    fn format(&self) -> String {
+        let sign = match self.0.sign() != Sign::Minus {
             true => "+",
-            false => "",
+            false => "-",
         };
-        format!("({}{}\r\n", sign, self.0).into_bytes()
+        format!("({}{}\r\n", sign, plain_digits(&self.0))
     }
 }
 
+impl RespEncode for BigNumber {
+    fn encode_to<B: BufMut>(&self, buf: &mut B) {
+        buf.put_slice(self.format().as_bytes());
+    }
+
+    fn encoded_len(&self) -> usize {
+        self.format().len()
+    }
+}
+
+// `BigDecimal`'s `Display` switches to exponent form (e.g. `1E+50`) for large and
+// small values, which is not a valid big number, so always spell out every digit.
+fn plain_digits(num: &BigDecimal) -> String {
+    let (int, scale) = num.as_bigint_and_exponent();
+    let digits = int.magnitude().to_string();
+    if scale <= 0 {
+        return format!("{}{}", digits, "0".repeat(scale.unsigned_abs() as usize));
+    }
+
+    let scale = scale as usize;
+    let digits = format!("{:0>width$}", digits, width = scale + 1);
+    let (int_part, frac_part) = digits.split_at(digits.len() - scale);
+    format!("{}.{}", int_part, frac_part)
+}
+
+// the length of `plain_digits`, computed without expanding the exponent
+fn plain_len(num: &BigDecimal) -> usize {
+    let (_, scale) = num.as_bigint_and_exponent();
+    let digits = num.digits();
+    let len = match scale {
+        ..=0 => digits.saturating_add(scale.unsigned_abs()),
+        // a leading `0.` when every digit is fractional
+        _ => digits.max(scale as u64 + 1) + 1,
+    };
+    usize::try_from(len).unwrap_or(usize::MAX)
+}
+
 impl RespDecode for BigNumber {
     const PREFIX: &'static str = "(";
     fn decode(buf: &mut bytes::BytesMut) -> Result<Self, RespError> {
         let end_idx = extract_simple_data_end_index(buf, Self::PREFIX)?;
         let data = buf.split_to(end_idx + CRLF_LEN);
         let s = String::from_utf8_lossy(&data[Self::PREFIX.len()..end_idx]);
-        let num = BigDecimal::from_str(&s)?;
-        Ok(BigNumber(num))
+        Self::new(&s)
     }
 
     fn expect_length(buf: &[u8]) -> Result<usize, RespError> {
@@ -79,4 +132,101 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn test_big_numbers_round_trip() -> Result<()> {
+        let cases = [
+            (
+                "3492890328409238509324850943850943825024385",
+                "(+3492890328409238509324850943850943825024385\r\n",
+            ),
+            (
+                "-3492890328409238509324850943850943825024385",
+                "(-3492890328409238509324850943850943825024385\r\n",
+            ),
+            (
+                "1e50",
+                "(+100000000000000000000000000000000000000000000000000\r\n",
+            ),
+            (
+                "1.23456789e-31",
+                "(+0.000000000000000000000000000000123456789\r\n",
+            ),
+            (
+                "-98765432109876543210.0123456789012345678901",
+                "(-98765432109876543210.0123456789012345678901\r\n",
+            ),
+            ("0", "(+0\r\n"),
+        ];
+        for (s, expected) in cases {
+            let encoded = BigNumber::new(s)?.encode();
+            assert_eq!(String::from_utf8_lossy(&encoded), expected);
+
+            let mut buf = bytes::BytesMut::from(&encoded[..]);
+            let frame = RespFrame::decode(&mut buf)?;
+            assert_eq!(frame, BigNumber::new(s)?.into());
+            assert!(buf.is_empty());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_big_numbers_exponent_limit() -> Result<()> {
+        // would expand to 9 GB of zeros
+        let mut buf = bytes::BytesMut::from(&b"(1e9000000000\r\n"[..]);
+        assert_eq!(
+            RespFrame::decode(&mut buf),
+            Err(RespError::BigNumberTooLong(9_000_000_001))
+        );
+        assert!(BigNumber::new("-1e-9000000000").is_err());
+        assert!(BigNumber::new(&format!("1e{}", i64::MAX)).is_err());
+
+        let limit = RespLimits::default().max_big_number_len;
+        let num = BigNumber::new(&format!("1e{}", limit - 1))?;
+        assert_eq!(num.to_plain_string().len(), limit);
+        assert_eq!(num.encoded_len(), limit + 4);
+        assert!(BigNumber::new(&format!("1e{}", limit)).is_err());
+        for s in [
+            "0",
+            "120",
+            "1e3",
+            "1.5",
+            "0.001",
+            "-123e-1",
+            "1.23456789e-31",
+        ] {
+            let num = BigNumber::new(s)?;
+            assert_eq!(plain_len(&num.0), plain_digits(&num.0).len());
+        }
+
+        let limits = RespLimits {
+            max_big_number_len: 3,
+            ..Default::default()
+        };
+        let mut buf = bytes::BytesMut::from(&b"(1e2\r\n(1e3\r\n"[..]);
+        let frame = RespFrame::decode_with_limits(&mut buf, &limits)?;
+        assert_eq!(frame, BigNumber::new("100")?.into());
+        assert_eq!(
+            RespFrame::decode_with_limits(&mut buf, &limits),
+            Err(RespError::BigNumberTooLong(4))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_big_numbers_in_array_decode() -> Result<()> {
+        let mut buf = bytes::BytesMut::new();
+        buf.extend_from_slice(b"*2\r\n(3492890328409238509324850943850943825024385\r\n:1\r\n");
+
+        let frame = RespFrame::decode(&mut buf)?;
+        assert_eq!(
+            frame,
+            crate::resp::RespFrameArray::new(vec![
+                BigNumber::new("3492890328409238509324850943850943825024385")?.into(),
+                1.into(),
+            ])
+            .into()
+        );
+        Ok(())
+    }
 }
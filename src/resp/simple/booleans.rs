@@ -1,14 +1,23 @@
+use bytes::BufMut;
+
 use crate::resp::{
-    extract_fixed_data, extract_simple_data_end_index, RespDecode, RespEncode, RespError, CRLF_LEN,
+    extract_fixed_data, extract_simple_data_end_index, RespDecode, RespEncode, RespError, CRLF,
+    CRLF_LEN,
 };
 
 impl RespEncode for bool {
-    fn encode(self) -> Vec<u8> {
+    fn encode_to<B: BufMut>(&self, buf: &mut B) {
         let value = match self {
-            true => "t",
-            false => "f",
+            true => b't',
+            false => b'f',
         };
-        format!("#{}\r\n", value).into_bytes()
+        buf.put_u8(b'#');
+        buf.put_u8(value);
+        buf.put_slice(CRLF);
+    }
+
+    fn encoded_len(&self) -> usize {
+        2 + CRLF_LEN
     }
 }
 
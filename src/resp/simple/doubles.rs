@@ -1,3 +1,5 @@
+use bytes::BufMut;
+
 use crate::resp::{extract_simple_data_end_index, RespDecode, RespEncode};
 
 impl RespDecode for f64 {
@@ -16,17 +18,37 @@ impl RespDecode for f64 {
 }
 
 impl RespEncode for f64 {
-    fn encode(self) -> Vec<u8> {
-        let mut buf = Vec::with_capacity(32);
-        let ret = if self.abs() > 1e+8 || self.abs() < 1e-8 {
-            format!(",{:+e}\r\n", self)
-        } else {
-            let sign = if self < 0.0 { "" } else { "+" };
-            format!(",{}{}\r\n", sign, self)
-        };
-
-        buf.extend_from_slice(&ret.into_bytes());
-        buf
+    fn encode_to<B: BufMut>(&self, buf: &mut B) {
+        buf.put_slice(format_double(*self).as_bytes());
+    }
+
+    fn encoded_len(&self) -> usize {
+        format_double(*self).len()
+    }
+}
+
+// `inf`, `-inf` and `nan` are spelled as the spec wants them, finite values carry an explicit
+// sign like integers do
+fn format_double(value: f64) -> String {
+    let sign = if value.is_finite() && value.is_sign_positive() {
+        "+"
+    } else {
+        ""
+    };
+    format!(",{}{}\r\n", sign, double_text(value))
+}
+
+/// The plain text of a double as redis replies it without a RESP3 double frame, e.g. `1.5`,
+/// `inf` or `1e300`. The digits are the shortest that parse back to exactly `value`.
+pub(crate) fn double_text(value: f64) -> String {
+    if value.is_nan() {
+        "nan".to_string()
+    } else if value.is_infinite() {
+        if value > 0.0 { "inf" } else { "-inf" }.to_string()
+    } else if value != 0.0 && (value.abs() >= 1e17 || value.abs() < 1e-5) {
+        format!("{:e}", value)
+    } else {
+        format!("{}", value)
     }
 }
 
@@ -47,12 +69,63 @@ mod tests {
         assert_eq!(frame.encode(), b",-123.456\r\n");
 
         let frame: RespFrame = 1.23456e+8.into();
-        assert_eq!(frame.encode(), b",+1.23456e8\r\n");
+        assert_eq!(frame.encode(), b",+123456000\r\n");
+
+        let frame: RespFrame = 1.5e300.into();
+        assert_eq!(frame.encode(), b",+1.5e300\r\n");
 
         let frame: RespFrame = (-1.23456e-9).into();
         assert_eq!(&frame.encode(), b",-1.23456e-9\r\n");
     }
 
+    #[test]
+    fn test_double_special_values() -> Result<()> {
+        let cases: [(f64, &[u8]); 6] = [
+            (0.0, b",+0\r\n"),
+            (-0.0, b",-0\r\n"),
+            (f64::INFINITY, b",inf\r\n"),
+            (f64::NEG_INFINITY, b",-inf\r\n"),
+            (f64::NAN, b",nan\r\n"),
+            (1.0, b",+1\r\n"),
+        ];
+        for (value, expected) in cases {
+            let frame: RespFrame = value.into();
+            assert_eq!(frame.encoded_len(), expected.len());
+            assert_eq!(frame.encode(), expected);
+        }
+
+        let mut buf = BytesMut::from(&b",inf\r\n,-inf\r\n,nan\r\n,1E3\r\n,-2.5e+2\r\n"[..]);
+        assert_eq!(f64::decode(&mut buf)?, f64::INFINITY);
+        assert_eq!(f64::decode(&mut buf)?, f64::NEG_INFINITY);
+        assert!(f64::decode(&mut buf)?.is_nan());
+        assert_eq!(f64::decode(&mut buf)?, 1000.0);
+        assert_eq!(f64::decode(&mut buf)?, -250.0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_double_round_trip() -> Result<()> {
+        let values = [
+            0.1,
+            -1.0 / 3.0,
+            f64::MAX,
+            f64::MIN_POSITIVE,
+            5e-324,
+            123456789.12345679,
+            1e16,
+            1e17,
+            1e-5,
+            9.99e-6,
+        ];
+        for value in values {
+            let mut buf = BytesMut::from(&RespFrame::from(value).encode()[..]);
+            assert_eq!(f64::decode(&mut buf)?.to_bits(), value.to_bits());
+        }
+
+        Ok(())
+    }
+
     #[test]
     fn test_double_decode() -> Result<()> {
         let mut buf = BytesMut::new();
@@ -1,18 +1,47 @@
 use std::ops::Deref;
 
-use crate::resp::{extract_simple_data_end_index, RespDecode, RespEncode, RespError, CRLF_LEN};
+use bytes::{BufMut, BytesMut};
+
+use crate::resp::{
+    check_line, decode_text, extract_simple_data_end_index, RespDecode, RespEncode, RespError,
+    CRLF, CRLF_LEN,
+};
 
 #[derive(Debug, PartialEq, PartialOrd)]
 pub struct SimpleError(pub String);
 
 impl SimpleError {
+    /// # Panics
+    ///
+    /// If `s` contains `\r` or `\n`, which a simple error can't carry. Use
+    /// [`SimpleError::try_new`] for text that may span lines.
     pub fn new(s: impl Into<String>) -> Self {
-        SimpleError(s.into())
+        let s = s.into();
+        assert!(
+            !s.contains(['\r', '\n']),
+            "simple error spans lines: {:?}",
+            s
+        );
+        SimpleError(s)
+    }
+
+    /// Like `new`, but returns an error for text containing `\r` or `\n`.
+    pub fn try_new(s: impl Into<String>) -> Result<Self, RespError> {
+        let s = s.into();
+        check_line(&s)?;
+        Ok(SimpleError(s))
+    }
+
+    /// Like [`RespDecode::decode`], but fails with [`RespError::Utf8Error`] instead of
+    /// replacing invalid utf-8.
+    pub fn decode_strict(buf: &mut BytesMut) -> Result<Self, RespError> {
+        let raw = RawSimpleError::decode(buf)?;
+        Ok(SimpleError(decode_text(raw.0, true)?))
     }
 }
 impl From<&str> for SimpleError {
     fn from(s: &str) -> Self {
-        SimpleError(s.to_string())
+        SimpleError::new(s)
     }
 }
 
@@ -24,18 +53,87 @@ impl Deref for SimpleError {
 }
 
 impl RespEncode for SimpleError {
-    fn encode(self) -> Vec<u8> {
-        format!("-{}\r\n", self.0).into_bytes()
+    fn encode_to<B: BufMut>(&self, buf: &mut B) {
+        buf.put_u8(b'-');
+        buf.put_slice(self.0.as_bytes());
+        buf.put_slice(CRLF);
+    }
+
+    fn encoded_len(&self) -> usize {
+        1 + self.0.len() + CRLF_LEN
     }
 }
 
 impl RespDecode for SimpleError {
     const PREFIX: &'static str = "-";
-    fn decode(buf: &mut bytes::BytesMut) -> Result<Self, RespError> {
+    fn decode(buf: &mut BytesMut) -> Result<Self, RespError> {
+        let raw = RawSimpleError::decode(buf)?;
+        Ok(SimpleError(decode_text(raw.0, false)?))
+    }
+
+    fn expect_length(buf: &[u8]) -> Result<usize, RespError> {
+        let end_idx = extract_simple_data_end_index(buf, Self::PREFIX)?;
+        Ok(end_idx + CRLF_LEN)
+    }
+}
+
+/// A simple error which keeps the bytes it was sent with, for peers that don't send utf-8.
+#[derive(Debug, PartialEq, PartialOrd)]
+pub struct RawSimpleError(pub Vec<u8>);
+
+impl RawSimpleError {
+    /// # Panics
+    ///
+    /// If `data` contains `\r` or `\n`, which a simple error can't carry.
+    pub fn new(data: impl Into<Vec<u8>>) -> Self {
+        let data = data.into();
+        assert!(
+            !data.iter().any(|b| matches!(b, b'\r' | b'\n')),
+            "simple error spans lines: {:?}",
+            String::from_utf8_lossy(&data)
+        );
+        RawSimpleError(data)
+    }
+}
+
+impl Deref for RawSimpleError {
+    type Target = Vec<u8>;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<SimpleError> for RawSimpleError {
+    fn from(s: SimpleError) -> Self {
+        RawSimpleError(s.0.into_bytes())
+    }
+}
+
+impl TryFrom<RawSimpleError> for SimpleError {
+    type Error = RespError;
+    fn try_from(raw: RawSimpleError) -> Result<Self, Self::Error> {
+        Ok(SimpleError(decode_text(raw.0, true)?))
+    }
+}
+
+impl RespEncode for RawSimpleError {
+    fn encode_to<B: BufMut>(&self, buf: &mut B) {
+        buf.put_u8(b'-');
+        buf.put_slice(&self.0);
+        buf.put_slice(CRLF);
+    }
+
+    fn encoded_len(&self) -> usize {
+        1 + self.0.len() + CRLF_LEN
+    }
+}
+
+impl RespDecode for RawSimpleError {
+    const PREFIX: &'static str = "-";
+    fn decode(buf: &mut BytesMut) -> Result<Self, RespError> {
         let end_idx = extract_simple_data_end_index(buf, Self::PREFIX)?;
         let data = buf.split_to(end_idx + CRLF_LEN);
-        let s = String::from_utf8_lossy(&data[Self::PREFIX.len()..end_idx]);
-        Ok(SimpleError::new(s.to_string()))
+        Ok(RawSimpleError(data[Self::PREFIX.len()..end_idx].to_vec()))
     }
 
     fn expect_length(buf: &[u8]) -> Result<usize, RespError> {
@@ -78,4 +176,28 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    #[should_panic(expected = "simple error spans lines")]
+    fn test_simple_error_new_line_breaks() {
+        SimpleError::new("ERR bad\nline");
+    }
+
+    #[test]
+    fn test_simple_error_lossless() -> Result<()> {
+        assert!(matches!(
+            SimpleError::try_new("ERR bad\nline"),
+            Err(RespError::InvalidFrame(_))
+        ));
+
+        let mut buf = BytesMut::from(&b"-ERR \xfe\r\n"[..]);
+        let raw = RawSimpleError::decode(&mut buf)?;
+        assert_eq!(&raw[..], b"ERR \xfe");
+        assert!(matches!(
+            SimpleError::try_from(raw),
+            Err(RespError::Utf8Error(_))
+        ));
+
+        Ok(())
+    }
 }
@@ -1,12 +1,24 @@
-use crate::resp::{extract_simple_data_end_index, RespDecode, RespEncode, RespError, CRLF_LEN};
+use bytes::BufMut;
+
+use crate::resp::{
+    decimal_len, extract_simple_data_end_index, put_decimal, RespDecode, RespEncode, RespError,
+    CRLF, CRLF_LEN,
+};
 
 impl RespEncode for i64 {
-    fn encode(self) -> Vec<u8> {
+    fn encode_to<B: BufMut>(&self, buf: &mut B) {
         let sign = match self.is_negative() {
-            true => "",
-            false => "+",
+            true => b'-',
+            false => b'+',
         };
-        format!(":{}{}\r\n", sign, self).into_bytes()
+        buf.put_u8(b':');
+        buf.put_u8(sign);
+        put_decimal(buf, self.unsigned_abs());
+        buf.put_slice(CRLF);
+    }
+
+    fn encoded_len(&self) -> usize {
+        2 + decimal_len(self.unsigned_abs()) + CRLF_LEN
     }
 }
 
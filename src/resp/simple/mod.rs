@@ -7,6 +7,7 @@ mod nulls;
 mod strings;
 
 pub use big_numbers::BigNumber;
-pub use errors::SimpleError;
+pub(crate) use doubles::double_text;
+pub use errors::{RawSimpleError, SimpleError};
 pub use nulls::SimpleNull;
-pub use strings::SimpleString;
+pub use strings::{RawSimpleString, SimpleString};
@@ -1,11 +1,17 @@
+use bytes::BufMut;
+
 use crate::resp::{extract_fixed_data, RespDecode, RespEncode, RespError};
 
 #[derive(Debug, PartialEq, PartialOrd)]
 pub struct SimpleNull;
 
 impl RespEncode for SimpleNull {
-    fn encode(self) -> Vec<u8> {
-        b"_\r\n".to_vec()
+    fn encode_to<B: BufMut>(&self, buf: &mut B) {
+        buf.put_slice(b"_\r\n");
+    }
+
+    fn encoded_len(&self) -> usize {
+        3
     }
 }
 
@@ -1,19 +1,48 @@
 use std::ops::Deref;
 
-use crate::resp::{extract_simple_data_end_index, RespDecode, RespEncode, RespError, CRLF_LEN};
+use bytes::{BufMut, BytesMut};
+
+use crate::resp::{
+    check_line, decode_text, extract_simple_data_end_index, RespDecode, RespEncode, RespError,
+    CRLF, CRLF_LEN,
+};
 
 #[derive(Debug, PartialEq, PartialOrd)]
 pub struct SimpleString(pub String);
 
 impl SimpleString {
+    /// # Panics
+    ///
+    /// If `s` contains `\r` or `\n`, which a simple string can't carry. Use
+    /// [`SimpleString::try_new`] for text that may span lines.
     pub fn new(s: impl Into<String>) -> Self {
-        SimpleString(s.into())
+        let s = s.into();
+        assert!(
+            !s.contains(['\r', '\n']),
+            "simple string spans lines: {:?}",
+            s
+        );
+        SimpleString(s)
+    }
+
+    /// Like `new`, but returns an error for text containing `\r` or `\n`.
+    pub fn try_new(s: impl Into<String>) -> Result<Self, RespError> {
+        let s = s.into();
+        check_line(&s)?;
+        Ok(SimpleString(s))
+    }
+
+    /// Like [`RespDecode::decode`], but fails with [`RespError::Utf8Error`] instead of
+    /// replacing invalid utf-8.
+    pub fn decode_strict(buf: &mut BytesMut) -> Result<Self, RespError> {
+        let raw = RawSimpleString::decode(buf)?;
+        Ok(SimpleString(decode_text(raw.0, true)?))
     }
 }
 
 impl From<&str> for SimpleString {
     fn from(s: &str) -> Self {
-        SimpleString(s.to_string())
+        SimpleString::new(s)
     }
 }
 
@@ -25,18 +54,87 @@ impl Deref for SimpleString {
 }
 
 impl RespEncode for SimpleString {
-    fn encode(self) -> Vec<u8> {
-        format!("+{}\r\n", self.0).into_bytes()
+    fn encode_to<B: BufMut>(&self, buf: &mut B) {
+        buf.put_u8(b'+');
+        buf.put_slice(self.0.as_bytes());
+        buf.put_slice(CRLF);
+    }
+
+    fn encoded_len(&self) -> usize {
+        1 + self.0.len() + CRLF_LEN
     }
 }
 
 impl RespDecode for SimpleString {
     const PREFIX: &'static str = "+";
-    fn decode(buf: &mut bytes::BytesMut) -> Result<Self, RespError> {
+    fn decode(buf: &mut BytesMut) -> Result<Self, RespError> {
+        let raw = RawSimpleString::decode(buf)?;
+        Ok(SimpleString(decode_text(raw.0, false)?))
+    }
+
+    fn expect_length(buf: &[u8]) -> Result<usize, RespError> {
+        let end_idx = extract_simple_data_end_index(buf, Self::PREFIX)?;
+        Ok(end_idx + CRLF_LEN)
+    }
+}
+
+/// A simple string which keeps the bytes it was sent with, for peers that don't send utf-8.
+#[derive(Debug, PartialEq, PartialOrd)]
+pub struct RawSimpleString(pub Vec<u8>);
+
+impl RawSimpleString {
+    /// # Panics
+    ///
+    /// If `data` contains `\r` or `\n`, which a simple string can't carry.
+    pub fn new(data: impl Into<Vec<u8>>) -> Self {
+        let data = data.into();
+        assert!(
+            !data.iter().any(|b| matches!(b, b'\r' | b'\n')),
+            "simple string spans lines: {:?}",
+            String::from_utf8_lossy(&data)
+        );
+        RawSimpleString(data)
+    }
+}
+
+impl Deref for RawSimpleString {
+    type Target = Vec<u8>;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<SimpleString> for RawSimpleString {
+    fn from(s: SimpleString) -> Self {
+        RawSimpleString(s.0.into_bytes())
+    }
+}
+
+impl TryFrom<RawSimpleString> for SimpleString {
+    type Error = RespError;
+    fn try_from(raw: RawSimpleString) -> Result<Self, Self::Error> {
+        Ok(SimpleString(decode_text(raw.0, true)?))
+    }
+}
+
+impl RespEncode for RawSimpleString {
+    fn encode_to<B: BufMut>(&self, buf: &mut B) {
+        buf.put_u8(b'+');
+        buf.put_slice(&self.0);
+        buf.put_slice(CRLF);
+    }
+
+    fn encoded_len(&self) -> usize {
+        1 + self.0.len() + CRLF_LEN
+    }
+}
+
+impl RespDecode for RawSimpleString {
+    const PREFIX: &'static str = "+";
+    fn decode(buf: &mut BytesMut) -> Result<Self, RespError> {
         let end_idx = extract_simple_data_end_index(buf, Self::PREFIX)?;
         let data = buf.split_to(end_idx + CRLF_LEN);
-        let s = String::from_utf8_lossy(&data[Self::PREFIX.len()..end_idx]);
-        Ok(SimpleString::new(s.to_string()))
+        Ok(RawSimpleString(data[Self::PREFIX.len()..end_idx].to_vec()))
     }
 
     fn expect_length(buf: &[u8]) -> Result<usize, RespError> {
@@ -79,4 +177,41 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    #[should_panic(expected = "simple string spans lines")]
+    fn test_simple_string_new_line_breaks() {
+        SimpleString::new("a\r\nb");
+    }
+
+    #[test]
+    fn test_simple_string_line_breaks() {
+        assert!(SimpleString::try_new("OK").is_ok());
+        assert!(matches!(
+            SimpleString::try_new("a\nb"),
+            Err(RespError::InvalidFrame(_))
+        ));
+    }
+
+    #[test]
+    fn test_simple_string_invalid_utf8() -> Result<()> {
+        let mut buf = BytesMut::from(&b"+a\xffb\r\n"[..]);
+        let frame = SimpleString::decode(&mut buf)?;
+        assert_eq!(frame, SimpleString::new("a\u{fffd}b"));
+
+        let mut buf = BytesMut::from(&b"+a\xffb\r\n"[..]);
+        let ret = SimpleString::decode_strict(&mut buf);
+        assert!(matches!(ret, Err(RespError::Utf8Error(_))));
+
+        let mut buf = BytesMut::from(&b"+a\xffb\r\n"[..]);
+        let raw = RawSimpleString::decode(&mut buf)?;
+        assert_eq!(raw, RawSimpleString::new(b"a\xffb".to_vec()));
+        assert!(SimpleString::try_from(raw).is_err());
+        assert_eq!(
+            RawSimpleString::new(b"a\xffb".to_vec()).encode(),
+            b"+a\xffb\r\n"
+        );
+
+        Ok(())
+    }
 }
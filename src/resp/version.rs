@@ -0,0 +1,209 @@
+use bytes::BufMut;
+
+use crate::resp::{
+    decimal_len, length_header_len, put_decimal, put_length_header, simple::double_text,
+    RespEncode, RespFrame, CRLF, CRLF_LEN,
+};
+
+const RESP2_NULL: &[u8] = b"$-1\r\n";
+
+/// The protocol a client speaks, RESP2 until it switches with `HELLO 3`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RespVersion {
+    Resp2,
+    #[default]
+    Resp3,
+}
+
+impl RespFrame {
+    /// Like [`RespEncode::encode`], but for a client speaking `version`.
+    pub fn encode_with_version(self, version: RespVersion) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(self.encoded_len_with_version(version));
+        self.encode_to_with_version(&mut buf, version);
+        buf
+    }
+
+    /// Writes the frame for a client speaking `version`. RESP3-only frames are downgraded to
+    /// their RESP2 equivalents the way redis replies to RESP2 clients: integers lose their
+    /// `+` sign, booleans become integers, doubles and big numbers become bulk strings, nulls
+    /// become `$-1`, maps and sets become flat arrays and attributes are dropped.
+    pub fn encode_to_with_version<B: BufMut>(&self, buf: &mut B, version: RespVersion) {
+        match version {
+            RespVersion::Resp2 => encode_resp2(self, buf),
+            RespVersion::Resp3 => self.encode_to(buf),
+        }
+    }
+
+    /// The exact number of bytes `encode_to_with_version` writes.
+    pub fn encoded_len_with_version(&self, version: RespVersion) -> usize {
+        match version {
+            RespVersion::Resp2 => resp2_len(self),
+            RespVersion::Resp3 => self.encoded_len(),
+        }
+    }
+}
+
+fn encode_resp2<B: BufMut>(frame: &RespFrame, buf: &mut B) {
+    match frame {
+        RespFrame::Nulls(_) => buf.put_slice(RESP2_NULL),
+        // hiredis, and so redis-cli, rejects the `+` sign RESP3 allows
+        RespFrame::Integers(i) => {
+            buf.put_u8(b':');
+            if *i < 0 {
+                buf.put_u8(b'-');
+            }
+            put_decimal(buf, i.unsigned_abs());
+            buf.put_slice(CRLF);
+        }
+        RespFrame::Booleans(b) => {
+            buf.put_slice(if *b { b":1\r\n" } else { b":0\r\n" });
+        }
+        RespFrame::Doubles(f) => put_bulk(buf, double_text(*f).as_bytes()),
+        RespFrame::BigNumbers(num) => put_bulk(buf, num.to_plain_string().as_bytes()),
+        RespFrame::VerbatimStrings(s) => put_bulk(buf, s),
+        // RESP2 has only single line errors, so the lines of a bulk error are joined
+        RespFrame::BulkErrors(e) => {
+            buf.put_u8(b'-');
+            for &b in e.iter() {
+                buf.put_u8(if matches!(b, b'\r' | b'\n') { b' ' } else { b });
+            }
+            buf.put_slice(CRLF);
+        }
+        RespFrame::Array(frames) => {
+            put_length_header(buf, b'*', frames.len());
+            frames.iter().for_each(|frame| encode_resp2(frame, buf));
+        }
+        RespFrame::Set(frames) => {
+            put_length_header(buf, b'*', frames.len());
+            frames.iter().for_each(|frame| encode_resp2(frame, buf));
+        }
+        RespFrame::Push(frames) => {
+            put_length_header(buf, b'*', frames.len());
+            frames.iter().for_each(|frame| encode_resp2(frame, buf));
+        }
+        RespFrame::Map(pairs) => {
+            put_length_header(buf, b'*', pairs.len() * 2);
+            for (key, value) in pairs.iter() {
+                encode_resp2(key, buf);
+                encode_resp2(value, buf);
+            }
+        }
+        RespFrame::Attribute(attribute) => encode_resp2(&attribute.frame, buf),
+        frame => frame.encode_to(buf),
+    }
+}
+
+fn resp2_len(frame: &RespFrame) -> usize {
+    match frame {
+        RespFrame::Nulls(_) => RESP2_NULL.len(),
+        RespFrame::Integers(i) => 1 + (*i < 0) as usize + decimal_len(i.unsigned_abs()) + CRLF_LEN,
+        RespFrame::Booleans(_) => 4,
+        RespFrame::Doubles(f) => bulk_len(double_text(*f).len()),
+        RespFrame::BigNumbers(num) => bulk_len(num.to_plain_string().len()),
+        RespFrame::VerbatimStrings(s) => bulk_len(s.len()),
+        RespFrame::BulkErrors(e) => 1 + e.len() + CRLF_LEN,
+        RespFrame::Array(frames) => {
+            length_header_len(frames.len()) + frames.iter().map(resp2_len).sum::<usize>()
+        }
+        RespFrame::Set(frames) => {
+            length_header_len(frames.len()) + frames.iter().map(resp2_len).sum::<usize>()
+        }
+        RespFrame::Push(frames) => {
+            length_header_len(frames.len()) + frames.iter().map(resp2_len).sum::<usize>()
+        }
+        RespFrame::Map(pairs) => {
+            length_header_len(pairs.len() * 2)
+                + pairs
+                    .iter()
+                    .map(|(key, value)| resp2_len(key) + resp2_len(value))
+                    .sum::<usize>()
+        }
+        RespFrame::Attribute(attribute) => resp2_len(&attribute.frame),
+        frame => frame.encoded_len(),
+    }
+}
+
+fn put_bulk<B: BufMut>(buf: &mut B, data: &[u8]) {
+    put_length_header(buf, b'$', data.len());
+    buf.put_slice(data);
+    buf.put_slice(CRLF);
+}
+
+fn bulk_len(len: usize) -> usize {
+    length_header_len(len) + len + CRLF_LEN
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::resp::*;
+    use anyhow::Result;
+
+    fn assert_resp2(frame: RespFrame, expected: &[u8]) {
+        assert_eq!(
+            frame.encoded_len_with_version(RespVersion::Resp2),
+            expected.len()
+        );
+        assert_eq!(frame.encode_with_version(RespVersion::Resp2), expected);
+    }
+
+    #[test]
+    fn test_resp2_downgrade_scalars() -> Result<()> {
+        assert_resp2(SimpleNull.into(), b"$-1\r\n");
+        assert_resp2(100.into(), b":100\r\n");
+        assert_resp2(i64::MIN.into(), b":-9223372036854775808\r\n");
+        assert_resp2(true.into(), b":1\r\n");
+        assert_resp2(false.into(), b":0\r\n");
+        assert_resp2(1.5.into(), b"$3\r\n1.5\r\n");
+        assert_resp2(f64::NEG_INFINITY.into(), b"$4\r\n-inf\r\n");
+        assert_resp2(
+            BigNumber::new("3492890328409238509324850943850943825024385")?.into(),
+            b"$43\r\n3492890328409238509324850943850943825024385\r\n",
+        );
+        assert_resp2(
+            VerbatimString::txt("Some string").into(),
+            b"$11\r\nSome string\r\n",
+        );
+        assert_resp2(
+            BulkError::new("SYNTAX invalid\r\nsyntax").into(),
+            b"-SYNTAX invalid  syntax\r\n",
+        );
+
+        // frames RESP2 already has are unchanged
+        assert_resp2(SimpleString::new("OK").into(), b"+OK\r\n");
+        assert_resp2(BulkNullString.into(), b"$-1\r\n");
+        assert_resp2(RespNullArray.into(), b"*-1\r\n");
+        assert_resp2(b"hello".into(), b"$5\r\nhello\r\n");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_resp2_downgrade_aggregates() {
+        let frame: RespFrame = RespMap::new(vec![
+            (b"a".into(), true.into()),
+            (b"b".into(), RespSet::new(vec![SimpleNull.into()]).into()),
+        ])
+        .into();
+        assert_resp2(frame, b"*4\r\n$1\r\na\r\n:1\r\n$1\r\nb\r\n*1\r\n$-1\r\n");
+
+        let frame: RespFrame = RespAttribute::new(
+            RespMap::new(vec![(b"ttl".into(), 100.into())]),
+            RespPush::new(vec![b"message".into(), 2.5.into()]),
+        )
+        .into();
+        assert_resp2(frame, b"*2\r\n$7\r\nmessage\r\n$3\r\n2.5\r\n");
+    }
+
+    #[test]
+    fn test_resp3_is_unchanged() {
+        let frame: RespFrame = RespMap::new(vec![(b"a".into(), true.into())]).into();
+        assert_eq!(
+            frame.encoded_len_with_version(RespVersion::Resp3),
+            frame.encoded_len()
+        );
+        assert_eq!(
+            frame.encode_with_version(RespVersion::Resp3),
+            b"%1\r\n$1\r\na\r\n#t\r\n"
+        );
+    }
+}